@@ -13,6 +13,7 @@ use rocket::http::ext::IntoCollection;
 use rocket::response::content::{RawCss, RawHtml, RawJavaScript};
 use rocket::response::stream::TextStream;
 use rocket::serde::uuid::Uuid;
+use viewy::bindings::rocket::csrf::{CsrfVerified, viewy_csrf_fairing};
 use viewy::bindings::rocket::static_assets::viewy_static_assets_fairing;
 use viewy::bindings::uri::Uri;
 use viewy::modifiers::Action;
@@ -25,6 +26,7 @@ use viewy::widgets::tabs::{Tab, TabContainer};
 use viewy::widgets::text::{Text, TextStyle};
 
 mod dynroutetest;
+mod menu;
 mod picker_select;
 mod sheet;
 mod tabs;
@@ -99,7 +101,7 @@ async fn home() -> Page<'static> {
 #[get("/actions")]
 async fn actions() -> Page<'static> {
     Page::with_title("Viewy showcase – Actions")
-        .with_layout(&|content: Node| {
+        .with_layout(|content: Node| {
             VStack::new(Alignment::Center)
                 .gap(vec![scale(5)])
                 .append_child(Text::new("Layout", TextStyle::Body))
@@ -130,37 +132,55 @@ async fn actions() -> Page<'static> {
                     Action::OpenPopover {
                         popover_content_url: Uri::from(uri!(popover_content())),
                     },
+                ))
+                .append_child(
+                    Button::new("Delete", ButtonStyle::Filled)
+                        .destructive()
+                        .on_click(Action::Confirm {
+                            title: "Delete this item?",
+                            message: "This action cannot be undone.",
+                            confirm_label: "Delete",
+                            cancel_label: "Cancel",
+                            destructive: true,
+                            action: Box::new(Action::Navigate {
+                                url: Uri::from(uri!(home())),
+                            }),
+                        }),
+                )
+                .append_child(Button::new("Archive", ButtonStyle::Outlined).on_click(
+                    Action::Request {
+                        method: RequestMethod::Post,
+                        url: Uri::from(uri!(archive())),
+                        body: RequestBody::Empty,
+                        then: Box::new(AfterRequest::ShowToast {
+                            message: "Item archived",
+                        }),
+                    },
                 ));
 
             main_stack
         })
 }
 
+#[post("/actions/archive")]
+async fn archive(_csrf: CsrfVerified) -> rocket::http::Status {
+    rocket::http::Status::NoContent
+}
+
 #[get("/popover-content")]
 async fn popover_content() -> Page<'static> {
-    Page::with_title("Viewy showcase – Actions").with_content({
-        let mut main_stack = VStack::new(Alignment::Stretch);
-        main_stack
-            .gap(vec![scale(4)])
-            .padding(vec![scale(5)])
-            .append_child(Text::new("Title", TextStyle::H1))
-            .append_child(Text::new("Text", TextStyle::Body))
-            .append_child(
-                HStack::new(Alignment::Center)
-                    .gap(vec![scale(4)])
-                    .append_child(
-                        Button::new("Cancel", ButtonStyle::Outlined)
-                            .on_click(Action::CloseParentWindow),
-                    )
-                    .append_child(Button::new("Ok", ButtonStyle::Filled).on_click(
-                        Action::OpenPopup {
-                            popup_content_url: Uri::from(uri!(home())),
-                            display_window_controls: false,
-                        },
-                    )),
-            );
-
-        main_stack
+    Page::with_title("Viewy showcase – Actions").with_content(view! {
+        <VStack(Alignment::Stretch) gap={vec![scale(4)]} padding={vec![scale(5)]}>
+            <Text("Title", TextStyle::H1)/>
+            <Text("Text", TextStyle::Body)/>
+            <HStack(Alignment::Center) gap={vec![scale(4)]}>
+                <Button("Cancel", ButtonStyle::Outlined) on_click={Action::CloseParentWindow}/>
+                <Button("Ok", ButtonStyle::Filled) on_click={Action::OpenPopup {
+                    popup_content_url: Uri::from(uri!(home())),
+                    display_window_controls: false,
+                }}/>
+            </HStack>
+        </VStack>
     })
 }
 
@@ -186,6 +206,7 @@ fn layout(content: Node) -> Node {
 }
 
 #[derive(Component, Clone)]
+#[component(name = "Boutons")]
 struct MyPage {
     pub btn_nbs: usize,
     pub btn_label: String,
@@ -199,10 +220,6 @@ impl Component for MyPage {
         }
         view.into()
     }
-
-    fn name() -> &'static str {
-        "Boutons"
-    }
 }
 
 #[get("/component")]
@@ -226,6 +243,7 @@ fn rocket() -> _ {
                 //hello,
                 component,
                 actions,
+                archive,
                 popover_content,
                 tabs::tabs,
                 tabs::tab1,
@@ -233,9 +251,11 @@ fn rocket() -> _ {
                 tabs::tab3,
                 sheet::sheet,
                 sheet::sheet_content,
-                picker_select::picker_select_demo
+                picker_select::picker_select_demo,
+                menu::menu_demo
             ],
         )
         .mount("/assets", FileServer::from(relative!("assets")))
         .attach(viewy_static_assets_fairing())
+        .attach(viewy_csrf_fairing(DoubleSubmitCookie))
 }
@@ -0,0 +1,63 @@
+use viewy::prelude::*;
+
+fn build_menu() -> Menu {
+    let mut menu = Menu::new();
+    menu.label("Document actions");
+
+    let mut edit = MenuSection::new("Edit");
+    edit.append_item(
+        MenuItem::new("Rename")
+            .icon(Lucide::Pencil)
+            .shortcut("F2")
+            .clone(),
+    )
+    .append_item(
+        MenuItem::new("Duplicate")
+            .icon(Lucide::Copy)
+            .shortcut("Ctrl+D")
+            .clone(),
+    );
+    menu.append_section(edit);
+
+    let mut view = MenuSection::new("View");
+    view.append_item(MenuItem::new("Show grid").checkable(true).clone())
+        .append_item(MenuItem::new("Show rulers").checkable(false).clone());
+    menu.append_section(view);
+
+    let mut share = Menu::new();
+    share
+        .append_item(MenuItem::new("Copy link").clone())
+        .append_item(MenuItem::new("Send by email").clone())
+        .append_item(MenuItem::new("Export").disabled().clone());
+    menu.append_item(MenuItem::new("Share").submenu(share).clone());
+
+    menu.append_separator().append_item(
+        MenuItem::new("Delete")
+            .icon(Lucide::Trash2)
+            .destructive()
+            .clone(),
+    );
+    menu
+}
+
+#[get("/menu")]
+pub fn menu_demo() -> Page<'static> {
+    Page::with_title("Viewy showcase – Menu").with_content({
+        let mut page = VStack::new(Alignment::Start);
+        page.gap(vec![scale(5)]).padding(vec![scale(5)]);
+
+        page.append_child(Text::new("Menu", TextStyle::H1));
+        page.append_child(Button::new("Actions", ButtonStyle::Outlined).menu(build_menu()));
+
+        let mut context_area = View::new();
+        context_area
+            .as_card(CardStyle::Outlined)
+            .width("300px")
+            .height("150px")
+            .on_contextmenu(Action::OpenMenu { menu: build_menu() });
+        context_area.text = Some("Right click here".to_string());
+        page.append_child(context_area);
+
+        page
+    })
+}
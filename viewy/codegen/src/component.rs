@@ -0,0 +1,383 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{
+    Data, DeriveInput, Expr, Fields, GenericArgument, Ident, LitStr, PathArguments, Type,
+    Visibility,
+};
+
+enum PropKind {
+    /// Must be set before `build` can be called
+    Required,
+    /// Defaults to `Default::default()` or to the given expression
+    Default(Option<Expr>),
+    /// `Option<T>` field, set with a `T`
+    Optional(Type),
+    /// `Vec<Node>` filled with `child`
+    Children,
+}
+
+struct Prop {
+    name: Ident,
+    ty: Type,
+    kind: PropKind,
+    into: bool,
+}
+
+/// `T` in `Option<T>`
+fn option_inner_type(ty: &Type) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(inner) => Some(inner.clone()),
+        _ => None,
+    }
+}
+
+fn parse_prop(field: &syn::Field) -> syn::Result<Prop> {
+    let name = field
+        .ident
+        .clone()
+        .ok_or_else(|| syn::Error::new(field.span(), "components must have named fields"))?;
+    let mut kind = PropKind::Required;
+    let mut into = false;
+
+    for attribute in &field.attrs {
+        if attribute.path().is_ident("children") {
+            kind = PropKind::Children;
+        } else if attribute.path().is_ident("prop") {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("default") {
+                    kind = PropKind::Default(if meta.input.peek(syn::Token![=]) {
+                        Some(meta.value()?.parse()?)
+                    } else {
+                        None
+                    });
+                    Ok(())
+                } else if meta.path.is_ident("optional") {
+                    let inner = option_inner_type(&field.ty).ok_or_else(|| {
+                        syn::Error::new(field.ty.span(), "optional props must be an `Option<T>`")
+                    })?;
+                    kind = PropKind::Optional(inner);
+                    Ok(())
+                } else if meta.path.is_ident("into") {
+                    into = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `default`, `default = ...`, `optional` or `into`"))
+                }
+            })?;
+        }
+    }
+
+    Ok(Prop {
+        name,
+        ty: field.ty.clone(),
+        kind,
+        into,
+    })
+}
+
+/// The `#[component(name = "...")]` override, the module path and struct name otherwise
+fn component_name(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let mut name = quote!(::core::concat!(
+        ::core::module_path!(),
+        "::",
+        ::core::stringify!(#ident)
+    ));
+    for attribute in &input.attrs {
+        if attribute.path().is_ident("component") {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    let value = meta.value()?.parse::<LitStr>()?;
+                    name = quote!(#value);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `name = \"...\"`"))
+                }
+            })?;
+        }
+    }
+    Ok(name)
+}
+
+/// Setter argument type and the expression converting `value` into the stored type
+fn setter_argument(ty: &Type, into: bool) -> (TokenStream2, TokenStream2) {
+    if into {
+        (
+            quote!(impl ::core::convert::Into<#ty>),
+            quote!(value.into()),
+        )
+    } else {
+        (quote!(#ty), quote!(value))
+    }
+}
+
+fn builder(input: &DeriveInput, props: &[Prop]) -> TokenStream2 {
+    let name = &input.ident;
+    let visibility = match &input.vis {
+        Visibility::Inherited => quote!(),
+        visibility => quote!(#visibility),
+    };
+    let builder = format_ident!("{}Builder", name);
+    let builder_doc = format!("Builder of [`{name}`], created with `{name}::builder()`");
+
+    let required = props
+        .iter()
+        .filter(|prop| matches!(prop.kind, PropKind::Required))
+        .collect::<Vec<&Prop>>();
+    // One type parameter per required prop: `()` until it is set, `(T,)` after
+    let states = (0..required.len())
+        .map(|index| format_ident!("__{}", index))
+        .collect::<Vec<Ident>>();
+    let unset = required.iter().map(|_| quote!(())).collect::<Vec<_>>();
+    let set = required
+        .iter()
+        .map(|prop| {
+            let ty = &prop.ty;
+            quote!((#ty,))
+        })
+        .collect::<Vec<_>>();
+
+    let required_names = required.iter().map(|prop| &prop.name).collect::<Vec<_>>();
+    let other = props
+        .iter()
+        .filter(|prop| !matches!(prop.kind, PropKind::Required))
+        .collect::<Vec<&Prop>>();
+    let other_names = other.iter().map(|prop| &prop.name).collect::<Vec<_>>();
+    let other_types = other
+        .iter()
+        .map(|prop| {
+            let ty = &prop.ty;
+            match prop.kind {
+                PropKind::Children | PropKind::Optional(_) => quote!(#ty),
+                _ => quote!(::core::option::Option<#ty>),
+            }
+        })
+        .collect::<Vec<_>>();
+    let other_initial = other
+        .iter()
+        .map(|prop| match prop.kind {
+            PropKind::Children => quote!(::std::vec::Vec::new()),
+            _ => quote!(::core::option::Option::None),
+        })
+        .collect::<Vec<_>>();
+    let other_values = other
+        .iter()
+        .map(|prop| {
+            let field = &prop.name;
+            match &prop.kind {
+                PropKind::Default(Some(default)) => {
+                    quote!(self.#field.unwrap_or_else(|| #default))
+                }
+                PropKind::Default(None) => quote!(self.#field.unwrap_or_default()),
+                _ => quote!(self.#field),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let required_setters = required.iter().enumerate().map(|(index, prop)| {
+        let field = &prop.name;
+        let ty = &prop.ty;
+        let (argument, value) = setter_argument(ty, prop.into);
+        let free_states = states
+            .iter()
+            .enumerate()
+            .filter(|(state_index, _)| *state_index != index)
+            .map(|(_, state)| state)
+            .collect::<Vec<_>>();
+        let before = states.iter().enumerate().map(|(state_index, state)| {
+            if state_index == index {
+                quote!(())
+            } else {
+                quote!(#state)
+            }
+        });
+        let after = states
+            .iter()
+            .enumerate()
+            .map(|(state_index, state)| {
+                if state_index == index {
+                    quote!((#ty,))
+                } else {
+                    quote!(#state)
+                }
+            })
+            .collect::<Vec<_>>();
+        let fields = required_names
+            .iter()
+            .enumerate()
+            .map(|(field_index, name)| {
+                if field_index == index {
+                    quote!(#name: (#value,))
+                } else {
+                    quote!(#name: self.#name)
+                }
+            });
+        // Components can be built with a struct expression, leaving the setters unused
+        quote_spanned! {field.span()=>
+            #[allow(dead_code)]
+            impl<#(#free_states),*> #builder<#(#before),*> {
+                pub fn #field(self, value: #argument) -> #builder<#(#after),*> {
+                    #builder {
+                        #(#fields,)*
+                        #(#other_names: self.#other_names,)*
+                    }
+                }
+            }
+        }
+    });
+
+    let other_setters = other.iter().map(|prop| {
+        let field = &prop.name;
+        match &prop.kind {
+            PropKind::Children => quote_spanned! {field.span()=>
+                pub fn child<C>(mut self, child: C) -> Self
+                where
+                    C: ::core::convert::Into<::viewy::node::Node>,
+                {
+                    self.#field.push(child.into());
+                    self
+                }
+            },
+            PropKind::Optional(inner) => {
+                let (argument, value) = setter_argument(inner, prop.into);
+                quote_spanned! {field.span()=>
+                    pub fn #field(mut self, value: #argument) -> Self {
+                        self.#field = ::core::option::Option::Some(#value);
+                        self
+                    }
+                }
+            }
+            _ => {
+                let (argument, value) = setter_argument(&prop.ty, prop.into);
+                quote_spanned! {field.span()=>
+                    pub fn #field(mut self, value: #argument) -> Self {
+                        self.#field = ::core::option::Option::Some(#value);
+                        self
+                    }
+                }
+            }
+        }
+    });
+
+    quote! {
+        #[doc = #builder_doc]
+        #[allow(non_camel_case_types)]
+        #visibility struct #builder<#(#states = ()),*> {
+            #(#required_names: #states,)*
+            #(#other_names: #other_types,)*
+        }
+
+        impl #name {
+            pub fn builder() -> #builder<#(#unset),*> {
+                #builder {
+                    #(#required_names: (),)*
+                    #(#other_names: #other_initial,)*
+                }
+            }
+        }
+
+        #(#required_setters)*
+
+        #[allow(dead_code, non_camel_case_types)]
+        impl<#(#states),*> #builder<#(#states),*> {
+            #(#other_setters)*
+        }
+
+        impl #builder<#(#set),*> {
+            pub fn build(self) -> #name {
+                #name {
+                    #(#required_names: self.#required_names.0,)*
+                    #(#other_names: #other_values,)*
+                }
+            }
+        }
+
+        impl ::core::convert::From<#builder<#(#set),*>> for ::viewy::node::Node {
+            fn from(builder: #builder<#(#set),*>) -> Self {
+                ::core::convert::Into::<::viewy::node::Node>::into(builder.build())
+            }
+        }
+    }
+}
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let component_name = component_name(input)?;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            name.span(),
+            "Component can only be derived for structs",
+        ));
+    };
+    let props = match &data.fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(parse_prop)
+            .collect::<syn::Result<Vec<Prop>>>()?,
+        _ => vec![],
+    };
+    if let Some(second_children) = props
+        .iter()
+        .filter(|prop| matches!(prop.kind, PropKind::Children))
+        .nth(1)
+    {
+        return Err(syn::Error::new(
+            second_children.name.span(),
+            "a component has at most one `#[children]` field",
+        ));
+    }
+    let builder = if props.is_empty() {
+        quote!()
+    } else {
+        builder(input, &props)
+    };
+    let append_child = props
+        .iter()
+        .find(|prop| matches!(prop.kind, PropKind::Children))
+        .map(|prop| {
+            let field = &prop.name;
+            quote! {
+                impl #name {
+                    /// Add a node to the children slot of the component
+                    pub fn append_child<C>(&mut self, child: C) -> &mut Self
+                    where
+                        C: ::core::convert::Into<::viewy::node::Node>,
+                    {
+                        self.#field.push(child.into());
+                        self
+                    }
+                }
+            }
+        });
+
+    Ok(quote! {
+        impl Into<Node> for #name {
+            fn into(self) -> Node {
+                self.render()
+            }
+        }
+
+        impl ::viewy::ComponentName for #name {
+            fn name() -> &'static str {
+                #component_name
+            }
+        }
+
+        #append_child
+
+        #builder
+    })
+}
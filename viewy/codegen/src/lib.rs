@@ -3,6 +3,9 @@ use quote::quote;
 use syn;
 use syn::{LitStr, Meta};
 
+mod component;
+mod view;
+
 #[proc_macro_derive(Widget, attributes(widget))]
 pub fn widget_derive(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree
@@ -36,6 +39,20 @@ pub fn widget_derive(input: TokenStream) -> TokenStream {
     }
 
     let style_str = style_value.expect("style is a mandatory attribute in widget macro");
+    let (script, script_registration) = match script_value {
+        Some(script) => (
+            quote! { Some(include_str!(#script)) },
+            quote! {
+                ::viewy::inventory::submit! {
+                    ::viewy::WidgetScript {
+                        widget: <#name as Widget>::ORIGIN,
+                        script: include_str!(#script),
+                    }
+                }
+            },
+        ),
+        None => (quote! { None }, quote! {}),
+    };
 
     let generated_code = quote! {
         use std::ops::{Deref, DerefMut};
@@ -45,6 +62,10 @@ pub fn widget_derive(input: TokenStream) -> TokenStream {
             fn into(self) -> Node {
                 let mut widget = self;
                 widget.render();
+                widget.node.origin = Some(<#name as Widget>::ORIGIN);
+                if let Some(script_id) = <#name as Widget>::script_id() {
+                    widget.node.attributes.insert("data-v-script".to_string(), script_id);
+                }
                 widget.node
             }
         }
@@ -52,6 +73,10 @@ pub fn widget_derive(input: TokenStream) -> TokenStream {
             fn into(self) -> Node {
                 let widget = self;
                 widget.render();
+                widget.node.origin = Some(<#name as Widget>::ORIGIN);
+                if let Some(script_id) = <#name as Widget>::script_id() {
+                    widget.node.attributes.insert("data-v-script".to_string(), script_id);
+                }
                 widget.node.clone()
             }
         }
@@ -70,38 +95,83 @@ pub fn widget_derive(input: TokenStream) -> TokenStream {
         }
 
         impl Widget for #name {
+            const ORIGIN: &'static str = concat!(module_path!(), "::", stringify!(#name));
             const STYLE: &'static str = include_str!(#style_str);
+            const SCRIPT: Option<&'static str> = #script;
             fn widget_name() -> &'static str {
-                "#name"
+                stringify!(#name)
             }
 
         }
-    };
-    generated_code.into()
-}
 
-#[proc_macro_derive(Component)]
-pub fn component_derive(input: TokenStream) -> TokenStream {
-    // Construct a representation of Rust code as a syntax tree
-    // that we can manipulate
-    let ast = syn::parse(input).unwrap();
-
-    // Build the trait implementation
-    impl_component_macro(&ast)
-}
-
-fn impl_component_macro(ast: &syn::DeriveInput) -> TokenStream {
-    let name = &ast.ident;
-    let generated_code = quote! {
-       impl Into<Node> for #name {
-            fn into(self) -> Node {
-                self.render()
+        ::viewy::inventory::submit! {
+            ::viewy::WidgetStyle {
+                widget: <#name as Widget>::ORIGIN,
+                style: <#name as Widget>::STYLE,
             }
         }
+
+        #script_registration
     };
     generated_code.into()
 }
 
+/// Implement `Into<Node>` and `ComponentName` for a component, and generate its props builder.
+///
+/// - `#[component(name = "...")]` overrides the component name, the module path and struct name by default
+/// - `#[prop(default)]` or `#[prop(default = expr)]` makes a prop optional in the builder
+/// - `#[prop(optional)]` on an `Option<T>` prop, the builder setter takes a `T`
+/// - `#[prop(into)]` makes the setter accept any `Into<T>`
+/// - `#[children]` on a `Vec<Node>` field gives the component an `append_child` method and the builder a `child` one,
+///   a component has at most one
+///
+/// ```rust,no_run
+/// # use viewy::prelude::*;
+/// # use viewy::Component;
+/// #[derive(Component)]
+/// struct Card {
+///     #[prop(into)]
+///     title: String,
+///     #[prop(default = ButtonStyle::Filled)]
+///     style: ButtonStyle,
+///     #[prop(optional, into)]
+///     subtitle: Option<String>,
+///     #[children]
+///     children: Vec<Node>,
+/// }
+/// # impl Component for Card {
+/// #     fn render(self) -> Node { View::new().into() }
+/// # }
+///
+/// let card = Card::builder()
+///     .title("Sales")
+///     .child(Text::new("12 000 €", TextStyle::H2))
+///     .build();
+/// ```
+/// `build` is only available once every required prop is set.
+///
+/// ```rust,compile_fail
+/// # use viewy::prelude::*;
+/// # use viewy::Component;
+/// #[derive(Component)]
+/// struct Split {
+///     #[children]
+///     left: Vec<Node>,
+///     #[children]
+///     right: Vec<Node>,
+/// }
+/// # impl Component for Split {
+/// #     fn render(self) -> Node { View::new().into() }
+/// # }
+/// ```
+#[proc_macro_derive(Component, attributes(component, prop, children))]
+pub fn component_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input!(input as syn::DeriveInput);
+    component::expand(&ast)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
 #[proc_macro_derive(Appendable)]
 pub fn appendable_derive(input: TokenStream) -> TokenStream {
     // Construct a representation of Rust code as a syntax tree
@@ -208,3 +278,38 @@ fn impl_cardifiable_macro(ast: &syn::DeriveInput) -> TokenStream {
     };
     generated_code.into()
 }
+
+/// Build a node tree with a JSX-like syntax, the macro returns a `Node`.
+///
+/// - `<Widget(args)>` calls `Widget::new(args)`, `<Component { fields }/>` builds a component with a struct literal
+/// - `modifier={value}`, `modifier("a", "b")` and `modifier` call the modifier methods of the widget
+/// - children are appended with `append_child`: elements, `{expr}` of any `Into<Node>`, `if` / `else` and `for` blocks
+/// - several roots are wrapped in a `Fragment`
+///
+/// ```rust,no_run
+/// # use viewy::prelude::*;
+/// # use viewy::Component;
+/// # #[derive(Component)]
+/// # struct ProjectCard {
+/// #     project: String,
+/// # }
+/// # impl Component for ProjectCard {
+/// #     fn render(self) -> Node { Text::new(&self.project, TextStyle::Body).into() }
+/// # }
+/// # let (can_archive, projects) = (true, vec!["Viewy".to_string()]);
+/// view! {
+///     <VStack(Alignment::Stretch) gap={vec![scale(4)]} padding={vec![scale(5)]}>
+///         <Text("Actions", TextStyle::H1)/>
+///         if can_archive {
+///             <Button("Archive", ButtonStyle::Outlined) on_click={Action::CloseParentWindow}/>
+///         }
+///         for project in projects {
+///             <ProjectCard { project }/>
+///         }
+///     </VStack>
+/// };
+/// ```
+#[proc_macro]
+pub fn view(input: TokenStream) -> TokenStream {
+    syn::parse_macro_input!(input as view::View).expand().into()
+}
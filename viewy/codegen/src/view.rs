@@ -0,0 +1,346 @@
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{Expr, Ident, Lit, Pat, Path, Token, braced, parenthesized};
+
+/// How the element is built from its path
+enum Constructor {
+    /// `<VStack(Alignment::Center)>` calls `VStack::new(Alignment::Center)`, `<Divider>` calls `Divider::new()`
+    New(Punctuated<Expr, Token![,]>),
+    /// `<UserCard { user }/>` builds the component with a struct literal
+    Struct(TokenStream2),
+}
+
+/// Modifier called on the element: `gap={vec![scale(4)]}`, `set_attr("role", "list")` or `disabled`
+struct Modifier {
+    name: Ident,
+    arguments: Vec<Expr>,
+}
+
+struct Element {
+    path: Path,
+    constructor: Constructor,
+    modifiers: Vec<Modifier>,
+    children: Vec<Child>,
+}
+
+enum Child {
+    Element(Element),
+    /// `{expr}`, anything convertible into a `Node`
+    Expr(Expr),
+    If {
+        condition: Expr,
+        then_branch: Vec<Child>,
+        else_branch: Option<Vec<Child>>,
+    },
+    For {
+        pattern: Pat,
+        iterator: Expr,
+        body: Vec<Child>,
+    },
+}
+
+/// Root of the `view!` macro
+pub struct View {
+    children: Vec<Child>,
+}
+
+impl Parse for View {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            children: parse_children(input)?,
+        })
+    }
+}
+
+fn parse_children(input: ParseStream) -> syn::Result<Vec<Child>> {
+    let mut children = vec![];
+    while !input.is_empty() {
+        children.push(input.parse()?);
+    }
+    Ok(children)
+}
+
+fn parse_block(input: ParseStream) -> syn::Result<Vec<Child>> {
+    let content;
+    braced!(content in input);
+    parse_children(&content)
+}
+
+impl Parse for Child {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Token![<]) {
+            return Ok(Child::Element(input.parse()?));
+        }
+        if input.peek(syn::token::Brace) {
+            let content;
+            braced!(content in input);
+            return Ok(Child::Expr(content.parse()?));
+        }
+        if input.peek(Token![if]) {
+            input.parse::<Token![if]>()?;
+            let condition = Expr::parse_without_eager_brace(input)?;
+            let then_branch = parse_block(input)?;
+            let else_branch = if input.peek(Token![else]) {
+                input.parse::<Token![else]>()?;
+                if input.peek(Token![if]) {
+                    Some(vec![input.parse()?])
+                } else {
+                    Some(parse_block(input)?)
+                }
+            } else {
+                None
+            };
+            return Ok(Child::If {
+                condition,
+                then_branch,
+                else_branch,
+            });
+        }
+        if input.peek(Token![for]) {
+            input.parse::<Token![for]>()?;
+            let pattern = Pat::parse_multi_with_leading_vert(input)?;
+            input.parse::<Token![in]>()?;
+            let iterator = Expr::parse_without_eager_brace(input)?;
+            let body = parse_block(input)?;
+            return Ok(Child::For {
+                pattern,
+                iterator,
+                body,
+            });
+        }
+        if input.peek(Lit) {
+            let text: Lit = input.parse()?;
+            return Err(syn::Error::new(
+                text.span(),
+                "text must be wrapped in a widget, like <Text(\"...\", TextStyle::Body)/>",
+            ));
+        }
+        Err(input.error("expected an element `<Widget>`, an expression `{...}`, `if` or `for`"))
+    }
+}
+
+impl Parse for Modifier {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        let arguments = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            if input.peek(syn::token::Brace) {
+                let content;
+                braced!(content in input);
+                vec![content.parse()?]
+            } else {
+                let value: Lit = input.parse()?;
+                vec![Expr::Lit(syn::ExprLit {
+                    attrs: vec![],
+                    lit: value,
+                })]
+            }
+        } else if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Punctuated::<Expr, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect()
+        } else {
+            vec![]
+        };
+        Ok(Self { name, arguments })
+    }
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let path = input.call(Path::parse_mod_style)?;
+        let constructor = if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Constructor::New(Punctuated::parse_terminated(&content)?)
+        } else if input.peek(syn::token::Brace) {
+            let content;
+            braced!(content in input);
+            Constructor::Struct(content.parse()?)
+        } else {
+            Constructor::New(Punctuated::new())
+        };
+
+        let mut modifiers = vec![];
+        while !(input.peek(Token![/]) || input.peek(Token![>])) {
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    path.span(),
+                    "unclosed element, expected `>` or `/>`",
+                ));
+            }
+            modifiers.push(input.parse()?);
+        }
+
+        if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+            return Ok(Self {
+                path,
+                constructor,
+                modifiers,
+                children: vec![],
+            });
+        }
+        input.parse::<Token![>]>()?;
+
+        let mut children = vec![];
+        loop {
+            if input.is_empty() {
+                return Err(syn::Error::new(
+                    path.span(),
+                    format!("unclosed element, expected `</{}>`", path_name(&path)),
+                ));
+            }
+            if input.peek(Token![<]) && input.peek2(Token![/]) {
+                break;
+            }
+            children.push(input.parse()?);
+        }
+        input.parse::<Token![<]>()?;
+        input.parse::<Token![/]>()?;
+        let closing_path = input.call(Path::parse_mod_style)?;
+        if path_name(&closing_path) != path_name(&path) {
+            return Err(syn::Error::new(
+                closing_path.span(),
+                format!("expected `</{}>`", path_name(&path)),
+            ));
+        }
+        input.parse::<Token![>]>()?;
+
+        Ok(Self {
+            path,
+            constructor,
+            modifiers,
+            children,
+        })
+    }
+}
+
+fn path_name(path: &Path) -> String {
+    path.to_token_stream().to_string().replace(' ', "")
+}
+
+/// Statements appending `children` to the widget in `parent`
+fn append_children(parent: &Ident, children: &[Child], depth: usize) -> TokenStream2 {
+    children
+        .iter()
+        .map(|child| append_child(parent, child, depth))
+        .collect()
+}
+
+fn append_child(parent: &Ident, child: &Child, depth: usize) -> TokenStream2 {
+    match child {
+        Child::Element(element) => {
+            let node = element.expand(depth + 1);
+            quote_spanned! {element.path.span()=>
+                #parent.append_child(#node);
+            }
+        }
+        Child::Expr(expr) => quote_spanned! {expr.span()=>
+            #parent.append_child(#expr);
+        },
+        Child::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            let then_branch = append_children(parent, then_branch, depth);
+            let else_branch = else_branch.as_ref().map(|else_branch| {
+                let else_branch = append_children(parent, else_branch, depth);
+                quote! { else { #else_branch } }
+            });
+            quote! {
+                if #condition { #then_branch } #else_branch
+            }
+        }
+        Child::For {
+            pattern,
+            iterator,
+            body,
+        } => {
+            let body = append_children(parent, body, depth);
+            quote! {
+                for #pattern in #iterator { #body }
+            }
+        }
+    }
+}
+
+impl Element {
+    /// Expression building the element, converted into a `Node`
+    fn expand(&self, depth: usize) -> TokenStream2 {
+        let path = &self.path;
+        let widget = format_ident!("__viewy_widget_{}", depth);
+        let constructor = match &self.constructor {
+            Constructor::New(arguments) => quote_spanned! {path.span()=>
+                #path::new(#arguments)
+            },
+            Constructor::Struct(fields) => quote_spanned! {path.span()=>
+                #path { #fields }
+            },
+        };
+        let modifiers = self.modifiers.iter().map(|modifier| {
+            let name = &modifier.name;
+            let arguments = &modifier.arguments;
+            quote_spanned! {name.span()=>
+                #widget.#name(#(#arguments),*);
+            }
+        });
+        let children = append_children(&widget, &self.children, depth);
+        // Widgets append their children with `Appendable`, components with their own `append_child`
+        let appendable = if self.children.is_empty() {
+            quote!()
+        } else {
+            quote! {
+                #[allow(unused_imports)]
+                use ::viewy::modifiers::Appendable as _;
+            }
+        };
+        let mutability = if self.modifiers.is_empty() && self.children.is_empty() {
+            quote!()
+        } else {
+            quote!(mut)
+        };
+
+        quote_spanned! {path.span()=>
+            {
+                #appendable
+                let #mutability #widget = #constructor;
+                #(#modifiers)*
+                #children
+                ::core::convert::Into::<::viewy::node::Node>::into(#widget)
+            }
+        }
+    }
+}
+
+impl View {
+    pub fn expand(&self) -> TokenStream2 {
+        match self.children.as_slice() {
+            [Child::Element(element)] => element.expand(0),
+            [Child::Expr(expr)] => quote_spanned! {expr.span()=>
+                ::core::convert::Into::<::viewy::node::Node>::into(#expr)
+            },
+            children => {
+                let fragment = format_ident!("__viewy_fragment");
+                let children = append_children(&fragment, children, 0);
+                quote! {
+                    {
+                        #[allow(unused_imports)]
+                        use ::viewy::modifiers::Appendable as _;
+                        let mut #fragment = ::viewy::widgets::fragment::Fragment::default();
+                        #children
+                        ::core::convert::Into::<::viewy::node::Node>::into(#fragment)
+                    }
+                }
+            }
+        }
+    }
+}
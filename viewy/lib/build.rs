@@ -12,8 +12,10 @@ use heck::{ToKebabCase, ToPascalCase, ToUpperCamelCase};
 use quote::{format_ident, quote};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
+use std::collections::hash_map::DefaultHasher;
 use std::env;
 use std::fs;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use uuid::Uuid;
@@ -302,9 +304,44 @@ fn generate_icon_pack(
     }
 }
 
+/// Hash the paths and contents of the files in `directory`, in a stable order
+fn hash_directory(directory: &Path, hasher: &mut DefaultHasher) {
+    let mut entries = fs::read_dir(directory)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    entries.sort();
+    for path in entries {
+        path.file_name().hash(hasher);
+        if path.is_dir() {
+            hash_directory(&path, hasher);
+        } else if let Ok(content) = fs::read(&path) {
+            content.hash(hasher);
+        }
+    }
+}
+
 fn main() {
     println!("cargo:rerun-if-env-changed=FORCE_REBUILD");
 
+    // Version of the static assets, part of their URLs so that they can be cached forever
+    let static_dir = PathBuf::from(
+        env::var("CARGO_MANIFEST_DIR")
+            .expect("Failed reading CARGO_MANIFEST_DIR environment variable"),
+    )
+    .join("static");
+    println!("cargo:rerun-if-changed={}", static_dir.display());
+    let mut static_hasher = DefaultHasher::new();
+    hash_directory(&static_dir, &mut static_hasher);
+    println!(
+        "cargo:rustc-env=STATIC_ASSETS_VERSION={:016x}",
+        static_hasher.finish()
+    );
+
     let root = project_root();
     for config_path in [
         root.join("Viewy.toml"),
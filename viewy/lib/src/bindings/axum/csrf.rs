@@ -0,0 +1,127 @@
+//! CSRF protection for axum
+//!
+//! Add the [`viewy_csrf`] middleware to issue a token for each client, extract the [`CsrfToken`] to
+//! embed it in pages with [`Page::with_csrf_token`](crate::prelude::Page::with_csrf_token) and
+//! the [`CsrfVerified`] extractor in the handlers called by
+//! [`Action::Request`](crate::modifiers::Action::Request).
+//!
+//! **Unlike the Rocket binding, pages don't get the token automatically**: a page rendered without
+//! [`Page::with_csrf_token`](crate::prelude::Page::with_csrf_token) has no `csrf-token` meta, and
+//! every request it sends is rejected with `403 Forbidden`.
+//! ```rust,no_run
+//! # use std::sync::Arc;
+//! # use axum::extract::Path;
+//! # use axum::http::StatusCode;
+//! # use axum::response::Html;
+//! # use axum::routing::{delete, get};
+//! # use axum::{Router, middleware};
+//! # use viewy::bindings::axum::csrf::{CsrfVerified, viewy_csrf};
+//! # use viewy::prelude::*;
+//! async fn project(csrf_token: CsrfToken) -> Html<String> {
+//!     Html(
+//!         Page::with_title("Project")
+//!             .with_csrf_token(csrf_token)
+//!             .compile(RenderMode::Complete),
+//!     )
+//! }
+//!
+//! async fn delete_project(_csrf: CsrfVerified, Path(id): Path<u32>) -> StatusCode {
+//!     StatusCode::NO_CONTENT
+//! }
+//!
+//! let source: Arc<dyn CsrfTokenSource> = Arc::new(DoubleSubmitCookie);
+//! let app: Router = Router::new()
+//!     .route("/projects/{id}", get(project).delete(delete_project))
+//!     .layer(middleware::from_fn_with_state(source, viewy_csrf));
+//! ```
+use std::sync::Arc;
+
+use axum::extract::{FromRequestParts, Request, State};
+use axum::http::header::SET_COOKIE;
+use axum::http::request::Parts;
+use axum::http::{HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::bindings::axum::cookie_value;
+use crate::core::csrf::{CSRF_COOKIE, CSRF_HEADER, CsrfToken, CsrfTokenSource};
+
+#[derive(Clone)]
+struct CsrfContext {
+    source: Arc<dyn CsrfTokenSource>,
+    stored: Option<String>,
+    token: CsrfToken,
+}
+
+/// Middleware issuing a CSRF token for each client, to use with `axum::middleware::from_fn_with_state`
+///
+/// The handlers rendering pages must extract the [`CsrfToken`] and pass it to
+/// [`Page::with_csrf_token`](crate::prelude::Page::with_csrf_token), the middleware can't add it to
+/// the page. Without it, the requests sent by [`Action::Request`](crate::modifiers::Action::Request)
+/// are rejected.
+pub async fn viewy_csrf(
+    State(source): State<Arc<dyn CsrfTokenSource>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let stored = cookie_value(request.headers(), CSRF_COOKIE);
+    let token = source.issue(stored.as_deref());
+    let is_new = stored.as_deref() != Some(token.as_str());
+
+    request.extensions_mut().insert(CsrfContext {
+        source,
+        stored,
+        token: token.clone(),
+    });
+    let mut response = next.run(request).await;
+
+    if is_new {
+        let cookie = format!(
+            "{}={}; Path=/; HttpOnly; SameSite=Strict",
+            CSRF_COOKIE,
+            token.as_str()
+        );
+        if let Ok(cookie) = HeaderValue::from_str(&cookie) {
+            response.headers_mut().append(SET_COOKIE, cookie);
+        }
+    }
+    response
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for CsrfToken {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<CsrfContext>()
+            .map(|context| context.token.clone())
+            .ok_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+/// Extractor rejecting the request with `403 Forbidden` when the CSRF token sent in the `x-csrf-token` header is invalid
+#[derive(Debug)]
+pub struct CsrfVerified;
+
+impl<S: Send + Sync> FromRequestParts<S> for CsrfVerified {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let context = parts
+            .extensions
+            .get::<CsrfContext>()
+            .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+        let provided = parts
+            .headers
+            .get(CSRF_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        if context.source.verify(context.stored.as_deref(), provided) {
+            Ok(CsrfVerified)
+        } else {
+            Err(StatusCode::FORBIDDEN)
+        }
+    }
+}
@@ -1 +1,20 @@
+use axum::http::HeaderMap;
+use axum::http::header::COOKIE;
+
+pub mod csrf;
+pub mod page;
+mod theme;
+
 pub(in crate::bindings) mod uri;
+
+/// Value of the cookie `name` sent with the request
+pub(in crate::bindings::axum) fn cookie_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(cookie_name, _)| *cookie_name == name)
+        .map(|(_, value)| value.to_string())
+}
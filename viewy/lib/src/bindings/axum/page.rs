@@ -0,0 +1,86 @@
+//! Implementations specific to Page struct for axum
+//!
+//! Extract a [`PageRequest`] and render the page with [`PageRequest::respond`]: like the Rocket
+//! responder, it renders the page in the mode asked by the client, with the theme picked by the
+//! user, and sets the `x-viewy-title` and `x-viewy-layout` headers that
+//! [`Page::with_boosted_navigation`] relies on.
+//! ```rust,no_run
+//! # use axum::response::Response;
+//! # use viewy::bindings::axum::page::PageRequest;
+//! # use viewy::prelude::*;
+//! async fn home(request: PageRequest) -> Response {
+//!     request.respond(Page::with_title("Home").with_boosted_navigation())
+//! }
+//! ```
+use std::convert::Infallible;
+
+use axum::body::Body;
+use axum::extract::FromRequestParts;
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE, VARY};
+use axum::http::request::Parts;
+use axum::response::Response;
+use futures::StreamExt;
+
+use crate::core::page::{Page, RenderMode, encode_header_value};
+use crate::core::theme::Theme;
+
+/// What the client asked for when loading a page, to render it with [`PageRequest::respond`]
+#[derive(Debug, Clone)]
+pub struct PageRequest {
+    render_mode: RenderMode,
+    theme: Theme,
+    path: String,
+}
+
+impl PageRequest {
+    /// Render `page` as a response, streamed when it has deferred components
+    pub fn respond(&self, mut page: Page<'_>) -> Response {
+        if *page.theme() == Theme::Auto {
+            page = page.with_theme(self.theme.clone());
+        }
+        if page.path().is_none() {
+            page = page.with_path(&self.path);
+        }
+        let title = encode_header_value(page.title());
+        let layout_id = page.layout_id();
+        let body = if page.is_streamed() {
+            Body::from_stream(
+                page.render_stream(self.render_mode)
+                    .map(Ok::<_, Infallible>),
+            )
+        } else {
+            Body::from(page.compile(self.render_mode))
+        };
+
+        Response::builder()
+            .header(CONTENT_TYPE, "text/html; charset=utf-8")
+            .header(CACHE_CONTROL, "max-age=3600, private")
+            .header(VARY, "x-viewy-render-mode, Cookie")
+            .header("x-viewy-title", title)
+            .header("x-viewy-layout", layout_id)
+            .body(body)
+            .expect("page headers are valid")
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for PageRequest {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let render_mode = match parts
+            .headers
+            .get("x-viewy-render-mode")
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("Complete")
+        {
+            "LayoutOnly" => RenderMode::LayoutOnly,
+            "ContentOnly" => RenderMode::ContentOnly,
+            _ => RenderMode::Complete,
+        };
+        Ok(Self {
+            render_mode,
+            theme: Theme::from_request_parts(parts, state).await?,
+            path: parts.uri.path().to_string(),
+        })
+    }
+}
@@ -0,0 +1,28 @@
+//! Theme picked by the user for axum
+//!
+//! Extract the [`Theme`] stored by a [`ThemeSwitcher`](crate::widgets::theme_switcher::ThemeSwitcher)
+//! to render the page with it, [`Theme::Auto`] when the user didn't pick one.
+//! ```rust,no_run
+//! # use axum::response::Html;
+//! # use viewy::prelude::*;
+//! async fn home(theme: Theme) -> Html<String> {
+//!     Html(Page::with_title("Home").with_theme(theme).compile(RenderMode::Complete))
+//! }
+//! ```
+use std::convert::Infallible;
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+
+use crate::bindings::axum::cookie_value;
+use crate::core::theme::{THEME_COOKIE, Theme};
+
+impl<S: Send + Sync> FromRequestParts<S> for Theme {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(cookie_value(&parts.headers, THEME_COOKIE)
+            .and_then(|theme| theme.parse().ok())
+            .unwrap_or(Theme::Auto))
+    }
+}
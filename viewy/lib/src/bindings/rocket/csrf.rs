@@ -0,0 +1,112 @@
+//! CSRF protection for Rocket
+//!
+//! Attach [`viewy_csrf_fairing`] to issue a token for each client, the [`Page`](crate::prelude::Page)
+//! responder then embeds it automatically. Add the [`CsrfVerified`] guard to the routes called by
+//! [`Action::Request`](crate::modifiers::Action::Request).
+//! ```rust,no_run
+//! # use rocket::http::Status;
+//! # use viewy::bindings::rocket::csrf::{CsrfVerified, viewy_csrf_fairing};
+//! # use viewy::prelude::DoubleSubmitCookie;
+//! #[rocket::delete("/projects/<id>")]
+//! fn delete_project(id: u32, _csrf: CsrfVerified) -> Status {
+//!     Status::NoContent
+//! }
+//!
+//! rocket::build()
+//!     .attach(viewy_csrf_fairing(DoubleSubmitCookie))
+//!     .mount("/", rocket::routes![delete_project]);
+//! ```
+use std::sync::Arc;
+
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{Cookie, SameSite, Status};
+use rocket::request::{FromRequest, Outcome};
+use rocket::{Build, Data, Request, Rocket};
+
+use crate::core::csrf::{CSRF_COOKIE, CSRF_HEADER, CsrfToken, CsrfTokenSource};
+
+struct CsrfState(Arc<dyn CsrfTokenSource>);
+
+pub struct CsrfFairing {
+    source: Arc<dyn CsrfTokenSource>,
+}
+
+/// Issue a CSRF token for each client with the given source
+pub fn viewy_csrf_fairing<S>(source: S) -> CsrfFairing
+where
+    S: CsrfTokenSource + 'static,
+{
+    CsrfFairing {
+        source: Arc::new(source),
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for CsrfFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Viewy CSRF protection",
+            kind: Kind::Ignite | Kind::Request,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        Ok(rocket.manage(CsrfState(self.source.clone())))
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _data: &mut Data<'_>) {
+        let stored = req
+            .cookies()
+            .get(CSRF_COOKIE)
+            .map(|cookie| cookie.value().to_string());
+        let token = self.source.issue(stored.as_deref());
+
+        if stored.as_deref() != Some(token.as_str()) {
+            req.cookies().add(
+                Cookie::build((CSRF_COOKIE, token.as_str().to_string()))
+                    .path("/")
+                    .http_only(true)
+                    .same_site(SameSite::Strict),
+            );
+        }
+        req.local_cache(|| Some(token));
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for CsrfToken {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request.local_cache(|| None::<CsrfToken>) {
+            Some(token) => Outcome::Success(token.clone()),
+            None => Outcome::Error((Status::InternalServerError, ())),
+        }
+    }
+}
+
+/// Request guard failing with `403 Forbidden` when the CSRF token sent in the `x-csrf-token` header is invalid
+#[derive(Debug)]
+pub struct CsrfVerified;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for CsrfVerified {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(CsrfState(source)) = request.rocket().state::<CsrfState>() else {
+            return Outcome::Error((Status::InternalServerError, ()));
+        };
+        let stored = request
+            .cookies()
+            .get(CSRF_COOKIE)
+            .map(|cookie| cookie.value());
+        let provided = request.headers().get_one(CSRF_HEADER).unwrap_or_default();
+
+        if source.verify(stored, provided) {
+            Outcome::Success(CsrfVerified)
+        } else {
+            Outcome::Error((Status::Forbidden, ()))
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub mod csrf;
+
 pub mod page;
 
 pub mod static_assets;
@@ -1,13 +1,18 @@
 //! Implementations specific to Page struct
-use crate::core::page::{Page, RenderMode};
+use crate::core::csrf::CsrfToken;
+use crate::core::page::{Page, RenderMode, encode_header_value};
+use crate::core::theme::{THEME_COOKIE, Theme};
+use futures::StreamExt;
 use rocket::http::ContentType;
 use rocket::http::hyper::header::CACHE_CONTROL;
 use rocket::log::private::info;
-use rocket::response::Responder;
+use rocket::response::{Responder, Response};
+use rocket::tokio;
+use rocket::tokio::io::AsyncWriteExt;
 use rocket::{Request, debug};
 
 impl<'r> Responder<'r, 'static> for Page<'_> {
-    fn respond_to(self, request: &'r Request<'_>) -> rocket::response::Result<'static> {
+    fn respond_to(mut self, request: &'r Request<'_>) -> rocket::response::Result<'static> {
         let render_mode = match request
             .headers()
             .get_one("x-viewy-render-mode")
@@ -18,11 +23,41 @@ impl<'r> Responder<'r, 'static> for Page<'_> {
             "Complete" | _ => RenderMode::Complete,
         };
         debug!("Rendering mode: {:?}", render_mode);
-        let page_content = self.compile(render_mode);
-        let mut response = page_content.respond_to(request)?;
+        if self.csrf_token.is_none() {
+            self.csrf_token = request.local_cache(|| None::<CsrfToken>).clone();
+        }
+        if *self.theme() == Theme::Auto
+            && let Some(theme) = request
+                .cookies()
+                .get(THEME_COOKIE)
+                .and_then(|cookie| cookie.value().parse::<Theme>().ok())
+        {
+            self = self.with_theme(theme);
+        }
+        let title = encode_header_value(self.title());
+        if self.path().is_none() {
+            self = self.with_path(request.uri().path().as_str());
+        }
+        let layout_id = self.layout_id();
+        let mut response = if self.is_streamed() {
+            let mut stream = self.render_stream(render_mode);
+            let (mut writer, reader) = tokio::io::duplex(16 * 1024);
+            tokio::spawn(async move {
+                while let Some(chunk) = stream.next().await {
+                    if writer.write_all(chunk.as_bytes()).await.is_err() {
+                        break;
+                    }
+                }
+            });
+            Response::build().streamed_body(reader).finalize()
+        } else {
+            self.compile(render_mode).respond_to(request)?
+        };
+        response.set_raw_header("x-viewy-title", title);
+        response.set_raw_header("x-viewy-layout", layout_id);
         response.set_header(ContentType::HTML);
         response.set_raw_header("Cache-Control", "max-age=3600, private");
-        response.set_raw_header("Vary", "x-viewy-render-mode");
+        response.set_raw_header("Vary", "x-viewy-render-mode, Cookie");
         Ok(response)
     }
 }
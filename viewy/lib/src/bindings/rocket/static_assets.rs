@@ -1,9 +1,16 @@
+use crate::core::assets::{
+    STATIC_VERSION, app_stylesheet_url, register_config_stylesheet, static_asset_url,
+    stylesheet_by_file_name,
+};
+use crate::core::config::Config;
 use rocket::fairing::{AdHoc, Fairing, Info, Kind};
 use rocket::fs::{FileServer, Options};
 use rocket::http::Header;
-use rocket::response::content::RawCss;
-use rocket::{Request, Response, Rocket, get, routes};
-use std::path::Path;
+use rocket::response::Redirect;
+use rocket::response::content::{RawCss, RawJavaScript};
+use rocket::{Request, Response, Rocket, get, info, routes};
+use std::env;
+use std::path::{Path, PathBuf};
 
 pub struct CacheFairing;
 
@@ -17,27 +24,96 @@ impl Fairing for CacheFairing {
     }
 
     async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
-        // Vérifie si la requête cible les fichiers statiques (ex: /static/*)
-        if req.uri().path().starts_with("/viewy-static/") && res.status().code == 200 {
-            res.set_header(Header::new("Cache-Control", "public, max-age=31536000"));
+        // Only fingerprinted URLs are cached, the redirections from the old URLs are not
+        let path = req.uri().path().as_str();
+        let is_fingerprinted = path.starts_with(&format!("/viewy-static/{STATIC_VERSION}/"))
+            || (path.starts_with("/viewy-static/app.") && path.ends_with(".css"));
+        if is_fingerprinted && res.status().code == 200 {
+            res.set_header(Header::new(
+                "Cache-Control",
+                "public, max-age=31536000, immutable",
+            ));
         }
     }
 }
 
-#[get("/app.css")]
-async fn get_stylesheet() -> RawCss<String> {
-    RawCss(crate::prelude::get_stylesheet())
+fn base_url() -> String {
+    env::var("BASE_URL").unwrap_or_default()
+}
+
+/// Serve the application and registered config stylesheets, `app.{hash}.css`, and redirect
+/// `app.css` to the application stylesheet. Other stylesheets, from a previous deploy or an unknown
+/// config, are not found rather than served with the wrong palette.
+#[get("/<file>")]
+async fn get_stylesheet(file: &str) -> Option<Result<RawCss<String>, Redirect>> {
+    if let Some(stylesheet) = stylesheet_by_file_name(file) {
+        Some(Ok(RawCss(stylesheet.css.clone())))
+    } else if file == "app.css" {
+        Some(Err(Redirect::to(format!(
+            "{}{}",
+            base_url(),
+            app_stylesheet_url()
+        ))))
+    } else {
+        None
+    }
+}
+
+/// Redirect the un-versioned URLs of the static files to their current version
+#[get("/<path..>", rank = 20)]
+async fn redirect_static_asset(path: PathBuf) -> Option<Redirect> {
+    if path.starts_with(STATIC_VERSION) {
+        return None;
+    }
+    let path = path.to_str()?;
+    Some(Redirect::to(format!(
+        "{}{}",
+        base_url(),
+        static_asset_url(path)
+    )))
+}
+
+#[get("/js/widgets/<file>")]
+async fn get_widget_script(file: &str) -> Option<RawJavaScript<&'static str>> {
+    let script_id = file.strip_suffix(".js")?;
+    crate::prelude::widget_script(script_id).map(RawJavaScript)
 }
 
 pub fn viewy_static_assets_fairing() -> AdHoc {
     println!("Viewy CARGO_MANIFEST_DIR = {}", env!("CARGO_MANIFEST_DIR"));
     AdHoc::on_ignite("Viewy Static Assets", |rocket| async move {
+        // Compile the stylesheet at startup rather than on the first request
+        info!("Viewy stylesheet: {}", app_stylesheet_url());
+        let versioned_base = format!("/viewy-static/{STATIC_VERSION}");
         rocket
             .attach(CacheFairing)
             .mount(
-                "/viewy-static/",
+                versioned_base.as_str(),
                 FileServer::from(Path::new(env!("CARGO_MANIFEST_DIR")).join("static")),
             )
-            .mount("/viewy-static", routes![get_stylesheet])
+            .mount(versioned_base.as_str(), routes![get_widget_script])
+            .mount(
+                "/viewy-static",
+                routes![get_stylesheet, redirect_static_asset],
+            )
+    })
+}
+
+/// Compile the stylesheets of the tenant configs at ignite, so that they are served by every
+/// instance before any page is rendered with them
+pub fn viewy_config_stylesheets_fairing(configs: Vec<Config>) -> AdHoc {
+    AdHoc::on_ignite("Viewy Config Stylesheets", |rocket| async move {
+        let urls = rocket::tokio::task::spawn_blocking(move || {
+            configs
+                .iter()
+                .map(|config| register_config_stylesheet(config).url())
+                .collect::<Vec<String>>()
+        })
+        .await
+        .expect("config stylesheets compilation panicked");
+        for url in urls {
+            info!("Viewy config stylesheet: {url}");
+        }
+        rocket
     })
 }
@@ -0,0 +1,140 @@
+//! Fingerprinted URLs of the viewy static assets
+//!
+//! Every asset URL contains a hash of its content, so that responses can be cached forever and
+//! a deploy changing an asset changes its URL.
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, LazyLock, RwLock};
+
+use crate::CONFIG;
+use crate::core::config::Config;
+use crate::core::theme::{compile_stylesheet, get_critical_stylesheet_from, theme_key};
+use crate::core::widget::WidgetStyle;
+
+/// Hash of the `static` directory, computed at build time.
+///
+/// Static files are served under `/viewy-static/{STATIC_VERSION}/`, which keeps the relative
+/// imports between javascript modules inside the same version.
+pub const STATIC_VERSION: &str = env!("STATIC_ASSETS_VERSION");
+
+/// Application stylesheet, compiled once from the palette and the registered widget styles
+pub struct CompiledStylesheet {
+    pub css: String,
+    /// Hash of `css`
+    pub hash: String,
+}
+
+impl CompiledStylesheet {
+    fn compile(config: &Config) -> Self {
+        let css = compile_stylesheet(config);
+        let mut hasher = DefaultHasher::new();
+        css.hash(&mut hasher);
+        Self {
+            css,
+            hash: format!("{:016x}", hasher.finish()),
+        }
+    }
+
+    /// Name of the stylesheet file, `app.{hash}.css`
+    pub fn file_name(&self) -> String {
+        format!("app.{}.css", self.hash)
+    }
+
+    /// URL of the stylesheet, relative to the base URL
+    pub fn url(&self) -> String {
+        format!("/viewy-static/{}", self.file_name())
+    }
+}
+
+static APP_STYLESHEET: LazyLock<Arc<CompiledStylesheet>> =
+    LazyLock::new(|| Arc::new(CompiledStylesheet::compile(&CONFIG)));
+
+/// Stylesheets of the configs registered with [`register_config_stylesheet`], by theme key.
+///
+/// Grows with the configs given to pages, never with the requests.
+static CONFIG_STYLESHEETS: LazyLock<RwLock<HashMap<u64, Arc<CompiledStylesheet>>>> =
+    LazyLock::new(Default::default);
+
+/// Compiled application stylesheet, the first call compiles it
+pub fn app_stylesheet() -> &'static CompiledStylesheet {
+    &APP_STYLESHEET
+}
+
+/// URL of the application stylesheet, relative to the base URL
+pub fn app_stylesheet_url() -> String {
+    app_stylesheet().url()
+}
+
+/// Compile the stylesheet with the palette and shapes of `config`, and the parts of its critical
+/// stylesheets, to serve several brands from one binary.
+///
+/// Call it at startup for each tenant config, before rendering the pages given the config with
+/// [`Page::with_config`](crate::prelude::Page::with_config): the stylesheet route only serves
+/// registered stylesheets, and a page rendered with an unregistered config compiles it first.
+pub fn register_config_stylesheet(config: &Config) -> Arc<CompiledStylesheet> {
+    if let Some(stylesheet) = config_stylesheet(config) {
+        return stylesheet;
+    }
+    let stylesheet = Arc::new(CompiledStylesheet::compile(config));
+    let stylesheet = CONFIG_STYLESHEETS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .entry(theme_key(config))
+        .or_insert(stylesheet)
+        .clone();
+    let widgets = inventory::iter::<WidgetStyle>
+        .into_iter()
+        .map(|widget_style| widget_style.widget);
+    get_critical_stylesheet_from(config, widgets);
+    stylesheet
+}
+
+/// Stylesheet of `config`, the application stylesheet for the theme of `Viewy.toml`, `None` if
+/// the config was not registered with [`register_config_stylesheet`]
+pub fn config_stylesheet(config: &Config) -> Option<Arc<CompiledStylesheet>> {
+    let key = theme_key(config);
+    if key == theme_key(&CONFIG) {
+        return Some(APP_STYLESHEET.clone());
+    }
+    CONFIG_STYLESHEETS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&key)
+        .cloned()
+}
+
+/// Whether the stylesheets of the theme of `key` are kept, the application theme or a registered one
+pub(crate) fn is_registered_theme(key: u64) -> bool {
+    key == theme_key(&CONFIG)
+        || CONFIG_STYLESHEETS
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .contains_key(&key)
+}
+
+/// Application or config stylesheet served at `/viewy-static/{file_name}`
+pub fn stylesheet_by_file_name(file_name: &str) -> Option<Arc<CompiledStylesheet>> {
+    if file_name == app_stylesheet().file_name() {
+        return Some(APP_STYLESHEET.clone());
+    }
+    CONFIG_STYLESHEETS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .values()
+        .find(|stylesheet| stylesheet.file_name() == file_name)
+        .cloned()
+}
+
+/// URL of a file of the `static` directory, relative to the base URL
+pub fn static_asset_url(path: &str) -> String {
+    format!(
+        "/viewy-static/{STATIC_VERSION}/{}",
+        path.trim_start_matches('/')
+    )
+}
+
+/// URL of a widget script, relative to the base URL
+pub fn widget_script_url(script_id: &str) -> String {
+    static_asset_url(&format!("js/widgets/{script_id}.js"))
+}
@@ -0,0 +1,373 @@
+//! Fragment cache for expensive, rarely changing [`Component`]s.
+//!
+//! Components are cached by type and props hash. A cache hit splices the already rendered HTML
+//! in the page without building the component tree.
+//! ```rust,no_run
+//! # use std::time::Duration;
+//! # use viewy::Component;
+//! # use viewy::prelude::*;
+//! # struct RedisStore;
+//! # impl RedisStore {
+//! #     fn new() -> Self { RedisStore }
+//! # }
+//! # impl FragmentStore for RedisStore {
+//! #     fn get(&self, _key: &str) -> Option<CachedFragment> { None }
+//! #     fn set(&self, _key: &str, _fragment: CachedFragment, _ttl: Option<Duration>) {}
+//! #     fn remove(&self, _key: &str) {}
+//! #     fn remove_prefix(&self, _prefix: &str) {}
+//! # }
+//! # #[derive(Hash)]
+//! # enum Role { Admin }
+//! #[derive(Component, Hash)]
+//! struct Sidebar {
+//!     role: Role,
+//! }
+//! # impl Component for Sidebar {
+//! #     fn render(self) -> Node { View::new().into() }
+//! # }
+//! # let (mut layout, role) = (View::new(), Role::Admin);
+//!
+//! // At startup, optional: the default cache keeps 1000 fragments in memory
+//! ComponentCache::install(ComponentCache::new(RedisStore::new()).default_ttl(Duration::from_secs(300)));
+//!
+//! layout.append_child(Sidebar { role }.cached());
+//!
+//! // When permissions change
+//! ComponentCache::global().invalidate_component::<Sidebar>();
+//! ```
+use std::any::type_name;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::component::Component;
+use crate::core::node::{Node, NodeType};
+use crate::core::widget::{SCRIPT_ATTRIBUTE, registered_widget};
+
+/// Rendered HTML of a component, with the icons, widgets and scripts it uses so that the page
+/// sprite, critical CSS and script preloads still include them
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedFragment {
+    pub html: String,
+    pub icon_ids: Vec<String>,
+    /// [`Node::origin`] of the widgets of the fragment
+    #[serde(default)]
+    pub origins: Vec<String>,
+    #[serde(default)]
+    pub script_ids: Vec<String>,
+}
+
+impl CachedFragment {
+    fn from_node(node: Node) -> Self {
+        let icon_ids = node
+            .iter()
+            .filter_map(|node| node.attributes.get("data-v-icon-id"))
+            .cloned()
+            .collect();
+        let mut origins: Vec<String> = vec![];
+        let mut script_ids: Vec<String> = vec![];
+        for node in node.iter() {
+            if let Some(origin) = node.origin
+                && !origins.iter().any(|known| known == origin)
+            {
+                origins.push(origin.to_string());
+            }
+            if let Some(script_id) = node.attributes.get(SCRIPT_ATTRIBUTE)
+                && !script_ids.contains(script_id)
+            {
+                script_ids.push(script_id.clone());
+            }
+        }
+        let mut html = String::new();
+        node.render(&mut html);
+        Self {
+            html,
+            icon_ids,
+            origins,
+            script_ids,
+        }
+    }
+
+    /// Fragment node rendering the cached HTML as is, with empty marker children standing for
+    /// the icons, widgets and scripts of the cached tree
+    fn into_node(self) -> Node {
+        let marker = || Node {
+            node_type: NodeType::Fragment,
+            ..Node::default()
+        };
+        let icons = self.icon_ids.into_iter().map(|icon_id| {
+            let mut icon = marker();
+            icon.attributes
+                .insert("data-v-icon-id".to_string(), icon_id);
+            icon
+        });
+        let widgets = self.origins.iter().filter_map(|origin| {
+            let mut widget = marker();
+            widget.origin = Some(registered_widget(origin)?);
+            Some(widget)
+        });
+        let scripts = self.script_ids.into_iter().map(|script_id| {
+            let mut script = marker();
+            script
+                .attributes
+                .insert(SCRIPT_ATTRIBUTE.to_string(), script_id);
+            script
+        });
+        Node {
+            node_type: NodeType::Fragment,
+            text: Some(self.html),
+            children: icons.chain(widgets).chain(scripts).collect(),
+            ..Node::default()
+        }
+    }
+}
+
+/// Where cached fragments are kept. Implement it to share the cache between servers (Redis, memcached…).
+///
+/// Keys are `{component type path}#{props hash}`, the same on every server running the same build.
+pub trait FragmentStore: Send + Sync {
+    fn get(&self, key: &str) -> Option<CachedFragment>;
+
+    fn set(&self, key: &str, fragment: CachedFragment, ttl: Option<Duration>);
+
+    fn remove(&self, key: &str);
+
+    /// Remove every fragment whose key starts with `prefix`
+    fn remove_prefix(&self, prefix: &str);
+}
+
+struct LruEntry {
+    fragment: CachedFragment,
+    expires_at: Option<Instant>,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct LruState {
+    entries: HashMap<String, LruEntry>,
+    /// Keys by last use, oldest first
+    usage: BTreeMap<u64, String>,
+    clock: u64,
+}
+
+impl LruState {
+    fn touch(&mut self, key: &str) {
+        self.clock += 1;
+        let clock = self.clock;
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.usage.remove(&entry.last_used);
+            entry.last_used = clock;
+            self.usage.insert(clock, key.to_string());
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.usage.remove(&entry.last_used);
+        }
+    }
+}
+
+/// In-memory store evicting the least recently used fragments beyond its capacity
+pub struct LruFragmentStore {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+impl LruFragmentStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.state().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, LruState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl FragmentStore for LruFragmentStore {
+    fn get(&self, key: &str) -> Option<CachedFragment> {
+        let mut state = self.state();
+        let expired = state
+            .entries
+            .get(key)?
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= Instant::now());
+        if expired {
+            state.remove(key);
+            return None;
+        }
+        state.touch(key);
+        state.entries.get(key).map(|entry| entry.fragment.clone())
+    }
+
+    fn set(&self, key: &str, fragment: CachedFragment, ttl: Option<Duration>) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut state = self.state();
+        state.remove(key);
+        while state.entries.len() >= self.capacity {
+            let Some((_, oldest)) = state.usage.pop_first() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+        state.entries.insert(
+            key.to_string(),
+            LruEntry {
+                fragment,
+                expires_at: ttl.map(|ttl| Instant::now() + ttl),
+                last_used: 0,
+            },
+        );
+        state.touch(key);
+    }
+
+    fn remove(&self, key: &str) {
+        self.state().remove(key);
+    }
+
+    fn remove_prefix(&self, prefix: &str) {
+        let mut state = self.state();
+        let keys = state
+            .entries
+            .keys()
+            .filter(|key| key.starts_with(prefix))
+            .cloned()
+            .collect::<Vec<String>>();
+        for key in keys {
+            state.remove(&key);
+        }
+    }
+}
+
+/// FNV-1a hasher: unlike `DefaultHasher`, its output doesn't change between Rust releases, so the
+/// keys of fragments shared between servers stay the same
+struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// Caches rendered components in a [`FragmentStore`]
+pub struct ComponentCache {
+    store: Box<dyn FragmentStore>,
+    default_ttl: Option<Duration>,
+}
+
+static GLOBAL_CACHE: OnceLock<ComponentCache> = OnceLock::new();
+
+impl ComponentCache {
+    pub fn new<S>(store: S) -> Self
+    where
+        S: FragmentStore + 'static,
+    {
+        Self {
+            store: Box::new(store),
+            default_ttl: None,
+        }
+    }
+
+    /// Time to live of the fragments cached without an explicit one, they never expire by default
+    pub fn default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+
+    /// Cache used by [`Cacheable::cached`], an in-memory LRU of 1000 fragments unless another one is installed
+    pub fn global() -> &'static ComponentCache {
+        GLOBAL_CACHE.get_or_init(|| ComponentCache::new(LruFragmentStore::new(1000)))
+    }
+
+    /// Replace the global cache, must be called before the first render using it.
+    ///
+    /// Gives the cache back if the global one is already in use.
+    pub fn install(cache: ComponentCache) -> Result<(), ComponentCache> {
+        GLOBAL_CACHE.set(cache)
+    }
+
+    fn key<C>(component: &C) -> String
+    where
+        C: Component + Hash,
+    {
+        let mut hasher = StableHasher::default();
+        component.hash(&mut hasher);
+        format!("{}#{:016x}", type_name::<C>(), hasher.finish())
+    }
+
+    pub fn render<C>(&self, component: C) -> Node
+    where
+        C: Component + Hash,
+    {
+        self.render_with_ttl(component, self.default_ttl)
+    }
+
+    pub fn render_with_ttl<C>(&self, component: C, ttl: Option<Duration>) -> Node
+    where
+        C: Component + Hash,
+    {
+        let key = Self::key(&component);
+        if let Some(fragment) = self.store.get(&key) {
+            return fragment.into_node();
+        }
+        let fragment = CachedFragment::from_node(component.render());
+        self.store.set(&key, fragment.clone(), ttl);
+        fragment.into_node()
+    }
+
+    /// Remove the fragment of a component rendered with these props
+    pub fn invalidate<C>(&self, component: &C)
+    where
+        C: Component + Hash,
+    {
+        self.store.remove(&Self::key(component));
+    }
+
+    /// Remove the fragments of a component, whatever its props
+    pub fn invalidate_component<C>(&self)
+    where
+        C: Component,
+    {
+        self.store.remove_prefix(&format!("{}#", type_name::<C>()));
+    }
+}
+
+/// Opt-in fragment caching, implemented for every hashable [`Component`]
+pub trait Cacheable: Component + Hash + Sized {
+    /// Render the component through the global [`ComponentCache`]
+    fn cached(self) -> Node {
+        ComponentCache::global().render(self)
+    }
+}
+
+impl<C> Cacheable for C where C: Component + Hash {}
@@ -1,4 +1,10 @@
-use crate::core::node::Node;
+use crate::core::node::{Node, NodeType};
+
+/// Name of a component. Implemented by `#[derive(Component)]`, with the module path and struct
+/// name (`app::sidebar::Sidebar`) or the one given with `#[component(name = "...")]`.
+pub trait ComponentName {
+    fn name() -> &'static str;
+}
 
 /// Define a struct as a `Component` to use it in `append_child` method of widgets
 /// ```rust
@@ -18,9 +24,54 @@ use crate::core::node::Node;
 ///         sub_component(&self).into()
 ///     }
 /// }
-pub trait Component: Into<Node> {
-    fn name() -> &'static str;
-
+pub trait Component: Into<Node> + ComponentName {
     /// You can write the template of your component with viewy basic widgets or other widgets
     fn render(self) -> Node;
 }
+
+/// A component rendered by a future, like a dashboard widget loading its data from a database.
+///
+/// Add it to a page with `Page::defer`: the page shell is streamed right away with the fallback in place
+/// of the component, which is streamed and swapped in as soon as it resolves.
+/// ```rust,no_run
+/// # use viewy::prelude::*;
+/// # struct PgPool;
+/// # async fn fetch_revenue(_pool: &PgPool) -> Vec<u32> { vec![] }
+/// # struct Chart;
+/// # impl Chart {
+/// #     fn new(_revenue: Vec<u32>) -> View { View::new() }
+/// # }
+/// # let pool = PgPool;
+/// struct RevenueChart {
+///     pool: PgPool,
+/// }
+///
+/// impl AsyncComponent for RevenueChart {
+///     fn fallback(&self) -> Node {
+///         Text::new("Loading…", TextStyle::Body).into()
+///     }
+///
+///     async fn render(self) -> Node {
+///         let revenue = fetch_revenue(&self.pool).await;
+///         Chart::new(revenue).into()
+///     }
+/// }
+///
+/// let (page, chart) = Page::with_title("Dashboard").defer(RevenueChart { pool });
+/// let page = page.with_content({
+///     let mut view = View::new();
+///     view.append_child(chart);
+///     view
+/// });
+/// ```
+pub trait AsyncComponent: Send + 'static {
+    /// Displayed until the component is rendered, nothing by default
+    fn fallback(&self) -> Node {
+        Node {
+            node_type: NodeType::Fragment,
+            ..Node::default()
+        }
+    }
+
+    fn render(self) -> impl Future<Output = Node> + Send;
+}
@@ -1,28 +1,47 @@
 use figment::providers::{Env, Format, Toml};
-use figment::value::{Dict, Map};
+use figment::value::{Dict, Map, Value};
 use figment::{Error, Figment, Metadata, Profile, Provider};
 use hex::FromHex;
 use palette::Srgba;
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
 
-#[derive(Serialize, Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub struct HexColor(pub [u8; 4]);
 
+/// Parse `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`, the `#` being optional
+impl FromStr for HexColor {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let digits = value.trim().trim_start_matches('#');
+        let digits = match digits.len() {
+            3 | 4 => digits.chars().flat_map(|digit| [digit, digit]).collect(),
+            _ => digits.to_string(),
+        };
+        let digits = if digits.len() == 6 {
+            format!("{digits}ff")
+        } else {
+            digits
+        };
+        <[u8; 4]>::from_hex(digits)
+            .map(Self)
+            .map_err(|_| format!("`{value}` is not a hex color like `#0052cc` or `#0052ccff`"))
+    }
+}
+
+/// Malformed colors are transparent, [`HexColor::from_str`] reports them
 impl From<&str> for HexColor {
     fn from(value: &str) -> Self {
-        Self(
-            <[u8; 4]>::from_hex(format!("{:f<8}", value.trim().replace("#", "")))
-                .unwrap_or([0, 0, 0, 0]),
-        )
+        value.parse().unwrap_or(Self([0, 0, 0, 0]))
     }
 }
 
 impl From<String> for HexColor {
     fn from(value: String) -> Self {
-        Self(
-            <[u8; 4]>::from_hex(format!("{:f<8}", value.trim().replace("#", "")))
-                .unwrap_or([0, 0, 0, 0]),
-        )
+        Self::from(value.as_str())
     }
 }
 
@@ -65,6 +84,16 @@ impl Into<Srgba<f32>> for HexColor {
     }
 }
 
+/// Serialized as `#rrggbbaa`, so that the defaults provider reads back
+impl Serialize for HexColor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
 impl<'de> Deserialize<'de> for HexColor {
     fn deserialize<D>(deserializer: D) -> Result<HexColor, D::Error>
     where
@@ -72,23 +101,23 @@ impl<'de> Deserialize<'de> for HexColor {
     {
         let color_string = String::deserialize(deserializer)?;
 
-        Ok(HexColor::from(color_string.as_str()))
+        color_string.parse().map_err(serde::de::Error::custom)
     }
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct AppSettings {
     pub name: String,
     pub favicons: Vec<Favicon>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Favicon {
     pub rel: String,
     pub href: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Colors {
     pub accent: ThemedColor,
     pub background: ThemedColor,
@@ -96,15 +125,22 @@ pub struct Colors {
     pub destructive: ThemedColor,
     pub success: ThemedColor,
     pub warning: ThemedColor,
+    /// Key colors of tonal palettes, derived from the accent when not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary: Option<HexColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tertiary: Option<HexColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub neutral: Option<HexColor>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ThemedColor {
     pub dark: HexColor,
     pub light: HexColor,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Shapes {
     #[serde(rename = "border-radius")]
     pub border_radius: i32,
@@ -112,6 +148,31 @@ pub struct Shapes {
     pub spacing_factor: i32,
 }
 
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct StyleSettings {
+    /// SCSS files, or directories of `.scss` and `.css` files, compiled with the widget styles
+    #[serde(default)]
+    pub sources: Vec<PathBuf>,
+}
+
+/// What to do with the contrast violations found when the stylesheet is compiled
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ContrastCheck {
+    #[default]
+    Off,
+    /// Print the violations
+    Warn,
+    /// Panic with the violations
+    Error,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+pub struct AccessibilitySettings {
+    #[serde(default, rename = "contrast-check")]
+    pub contrast_check: ContrastCheck,
+}
+
 /// App and theme configuration
 ///
 /// # Viewy.toml breakdown
@@ -134,9 +195,17 @@ pub struct Shapes {
 ///
 /// success = { light = "#3DA144 ", dark = "#3DA144" }
 ///
+/// secondary = "#5b5f71"
+///
 /// [shapes]
 /// border-radius = 8
 /// spacing-factor = 4
+///
+/// [styles]
+/// sources = ["styles", "assets/print.scss"]
+///
+/// [accessibility]
+/// contrast-check = "warn"
 /// ```
 ///
 /// #### [app]
@@ -153,6 +222,11 @@ pub struct Shapes {
 ///
 /// - The main color (e.g., `accent`, `background`, `surface`, `destructive`, `success`).
 ///
+/// The optional `secondary`, `tertiary` and `neutral` key colors are single colors. With the light
+/// variants of `accent`, `destructive`, `success` and `warning`, they generate tonal palettes,
+/// the CSS variables `--{key}-{tone}` like `--secondary-90`, for the tones of [`TONES`](crate::prelude::TONES).
+/// When not set, they are derived from the accent.
+///
 /// #### [shapes]
 ///
 /// This section defines the geometric properties of widgets:
@@ -161,34 +235,232 @@ pub struct Shapes {
 ///
 /// - `spacing-factor`: Defines the factor by which spaces between UI widgets are multiplied. A factor of 4 can be used to derive consistent spacings like 4, 8, 12, 16, etc.
 ///
+/// #### [styles]
+///
+/// Application styles, compiled in the same pass as the widget styles into `app.css`:
+///
+/// - `sources`: SCSS files and directories, relative to the working directory. The `.scss` and `.css` files of a directory are compiled in path order, except the partials starting with `_`, which can be imported from the other files. The `sp()` and `scale()` functions and the color variables like `var(--accent)` are available.
+///
+/// #### [accessibility]
+///
+/// - `contrast-check`: `"off"` (the default), `"warn"` or `"error"`. When enabled, the contrast of the text colors on the backgrounds the widgets use them with is checked against WCAG AA, in both themes, when the stylesheet is compiled. Violations are printed with a suggested color, or make the compilation panic. See [`audit_contrast`](crate::prelude::audit_contrast).
+///
 /// ---
 ///
 /// Using the `Viewy.toml` file, developers can ensure that their applications maintain a consistent look and feel across different pages and widgets. It acts as a central place to modify and adapt the visual language of the application according to branding or aesthetic requirements.
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Config {
     pub app: AppSettings,
     pub colors: Colors,
     pub shapes: Shapes,
+    #[serde(default)]
+    pub styles: StyleSettings,
+    #[serde(default)]
+    pub accessibility: AccessibilitySettings,
+}
+
+/// Problem with a configuration key
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    /// Dotted path of the key, like `shapes.border-radius` or `app.favicons[0].href`
+    pub key: String,
+    /// File or provider the value comes from, when known
+    pub source: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.key, self.message)?;
+        if let Some(source) = &self.source {
+            write!(f, " (in {source})")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// A config file can't be read, or a value has the wrong type or format
+    Parse(Box<Error>),
+    /// Values are well formed but can't be used
+    Invalid(Vec<ConfigIssue>),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Parse(error) => {
+                write!(f, "Viewy config can't be loaded:")?;
+                for error in error.as_ref().clone() {
+                    write!(f, "\n  - {error}")?;
+                }
+                Ok(())
+            }
+            ConfigError::Invalid(issues) => {
+                write!(f, "Viewy config is invalid:")?;
+                for issue in issues {
+                    write!(f, "\n  - {issue}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<Error> for ConfigError {
+    fn from(error: Error) -> Self {
+        ConfigError::Parse(Box::new(error))
+    }
+}
+
+/// Top level keys read by the viewy build script rather than by [`Config`]
+const BUILD_KEYS: [&str; 1] = ["icon-packs"];
+
+/// Keys of `values` missing from `known`, the content of arrays not being checked
+fn collect_unknown_keys(prefix: &str, values: &Dict, known: &Dict, unknown: &mut Vec<String>) {
+    for (key, value) in values {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match (value, known.get(key)) {
+            (_, None) => unknown.push(path),
+            (Value::Dict(_, values), Some(Value::Dict(_, known))) => {
+                collect_unknown_keys(&path, values, known, unknown)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// File the value of `key` comes from, or the name of its provider
+fn key_source(figment: &Figment, key: &str) -> Option<String> {
+    figment.find_metadata(key).map(|metadata| {
+        metadata
+            .source
+            .as_ref()
+            .map(|source| source.to_string())
+            .unwrap_or_else(|| metadata.name.to_string())
+    })
 }
 
 impl Config {
-    pub fn load() -> Self {
-        let figment = Figment::from({
+    /// Providers of the configuration: the defaults, `Viewy.toml`, `viewy.toml` and the `VIEWY_` environment variables
+    pub fn figment() -> Figment {
+        Figment::from({
             Figment::new()
                 .merge(Config::default())
                 .merge(Toml::file("Viewy.toml"))
                 .merge(Toml::file("viewy.toml"))
         })
-        .merge(Env::prefixed("VIEWY_").split("_"));
+        .merge(Env::prefixed("VIEWY_").split("_"))
+    }
 
-        let config = figment
-            .extract::<Config>()
-            .map_err(|err| {
-                println!("Viewy config error {:?}", err);
-                err
+    /// Load the configuration, panicking with the errors found
+    pub fn load() -> Self {
+        Self::try_load().unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Load and validate the configuration, printing a warning for each unknown key
+    pub fn try_load() -> Result<Self, ConfigError> {
+        Self::try_from_figment(&Self::figment())
+    }
+
+    /// Load and validate the configuration from `figment`, printing a warning for each unknown key
+    pub fn try_from_figment(figment: &Figment) -> Result<Self, ConfigError> {
+        for issue in Self::unknown_keys(figment) {
+            eprintln!("Viewy config warning: {issue}");
+        }
+        let config = figment.extract::<Config>()?;
+        config.validate().map_err(|err| match err {
+            ConfigError::Invalid(issues) => ConfigError::Invalid(
+                issues
+                    .into_iter()
+                    .map(|issue| {
+                        // Metadata is only tracked down to arrays
+                        let key = issue.key.split('[').next().unwrap_or_default();
+                        ConfigIssue {
+                            source: key_source(figment, key),
+                            ..issue
+                        }
+                    })
+                    .collect(),
+            ),
+            err => err,
+        })?;
+        Ok(config)
+    }
+
+    /// Keys of `figment` that viewy doesn't use, most likely typos
+    pub fn unknown_keys(figment: &Figment) -> Vec<ConfigIssue> {
+        let mut reference = Config::default();
+        // Optional keys aren't serialized when unset
+        reference.colors.secondary = Some(HexColor([0, 0, 0, 0]));
+        reference.colors.tertiary = Some(HexColor([0, 0, 0, 0]));
+        reference.colors.neutral = Some(HexColor([0, 0, 0, 0]));
+        let known = Figment::from(reference)
+            .extract::<Dict>()
+            .unwrap_or_default();
+        let values = figment.extract::<Dict>().unwrap_or_default();
+        let mut unknown = vec![];
+        collect_unknown_keys("", &values, &known, &mut unknown);
+        unknown
+            .into_iter()
+            .filter(|key| !BUILD_KEYS.contains(&key.as_str()))
+            .map(|key| ConfigIssue {
+                source: key_source(figment, &key),
+                key,
+                message: "unknown key".to_string(),
+            })
+            .collect()
+    }
+
+    /// Check the values that deserialize but can't be used, to run from a test or a CI step
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut issues = vec![];
+        let mut issue = |key: String, message: &str| {
+            issues.push(ConfigIssue {
+                key,
+                source: None,
+                message: message.to_string(),
             })
-            .unwrap();
-        config
+        };
+
+        if self.shapes.border_radius < 0 {
+            issue("shapes.border-radius".to_string(), "must not be negative");
+        }
+        if self.shapes.spacing_factor < 0 {
+            issue("shapes.spacing-factor".to_string(), "must not be negative");
+        }
+        for (index, favicon) in self.app.favicons.iter().enumerate() {
+            if favicon.rel.trim().is_empty() {
+                issue(format!("app.favicons[{index}].rel"), "must not be empty");
+            }
+            if !favicon.href.starts_with('/') {
+                issue(
+                    format!("app.favicons[{index}].href"),
+                    "must be a path starting with `/`, it is prefixed with the base url",
+                );
+            }
+        }
+        for (index, source) in self.styles.sources.iter().enumerate() {
+            if !source.exists() {
+                issue(
+                    format!("styles.sources[{index}]"),
+                    &format!("`{}` doesn't exist", source.display()),
+                );
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(issues))
+        }
     }
 }
 
@@ -228,11 +500,16 @@ impl Default for Config {
                     dark: "#FFB073".into(),
                     light: "#DF7B5E".into(),
                 },
+                secondary: None,
+                tertiary: None,
+                neutral: None,
             },
             shapes: Shapes {
                 border_radius: 8,
                 spacing_factor: 4,
             },
+            styles: StyleSettings::default(),
+            accessibility: AccessibilitySettings::default(),
         }
     }
 }
@@ -243,7 +520,7 @@ impl Provider for Config {
     }
 
     fn data(&self) -> Result<Map<Profile, Dict>, Error> {
-        figment::providers::Serialized::defaults(Config::default()).data()
+        figment::providers::Serialized::defaults(self).data()
     }
 
     fn profile(&self) -> Option<Profile> {
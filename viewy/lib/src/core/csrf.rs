@@ -0,0 +1,59 @@
+//! Cross-site request forgery protection for [`Action::Request`](crate::modifiers::Action::Request).
+//!
+//! The token is embedded in the rendered page (`<meta name="csrf-token">`) and sent back by the
+//! javascript in the [`CSRF_HEADER`] header. The http framework bindings populate the token of each
+//! page and provide a guard (Rocket) or an extractor (axum) to verify it.
+use uuid::Uuid;
+
+/// Header used by the javascript to send the token back
+pub const CSRF_HEADER: &str = "x-csrf-token";
+/// Cookie storing the token of the current client
+pub const CSRF_COOKIE: &str = "viewy_csrf";
+
+/// CSRF token of the current client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrfToken(String);
+
+impl CsrfToken {
+    pub fn new(token: &str) -> Self {
+        Self(token.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Where tokens come from and how they are checked.
+///
+/// The bindings store the issued token in the [`CSRF_COOKIE`] cookie and give it back as `stored`.
+/// Implement this trait to derive tokens from your own session store instead.
+pub trait CsrfTokenSource: Send + Sync {
+    /// Returns the token to use for this client, `stored` being the one already stored in its cookie.
+    fn issue(&self, stored: Option<&str>) -> CsrfToken;
+
+    /// Checks the token sent back by the client.
+    fn verify(&self, stored: Option<&str>, provided: &str) -> bool {
+        stored.is_some_and(|stored| constant_time_eq(stored.as_bytes(), provided.as_bytes()))
+    }
+}
+
+/// Default [`CsrfTokenSource`]: a random token kept in a cookie and compared to the one sent with the request.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DoubleSubmitCookie;
+
+impl CsrfTokenSource for DoubleSubmitCookie {
+    fn issue(&self, stored: Option<&str>) -> CsrfToken {
+        match stored {
+            Some(token) if !token.is_empty() => CsrfToken::new(token),
+            _ => CsrfToken(Uuid::new_v4().simple().to_string()),
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
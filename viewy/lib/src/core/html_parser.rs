@@ -0,0 +1,175 @@
+use ego_tree::NodeRef;
+use html_escape::{encode_double_quoted_attribute, encode_text};
+use scraper::Html;
+
+use crate::core::node::{Node, NodeType, intern_tag};
+
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Elements whose text content is not escaped
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+fn text_node(text: String) -> Node {
+    Node {
+        node_type: NodeType::Fragment,
+        text: Some(text),
+        ..Node::default()
+    }
+}
+
+/// Declarations of a `style` attribute, `None` when it can't be split safely: unbalanced quotes
+/// or parentheses, escapes, or a declaration without value.
+///
+/// `;` inside quotes and parentheses, like in `url(data:image/png;base64,…)`, are part of the value.
+fn style_declarations(style: &str) -> Option<Vec<(String, String)>> {
+    let mut declarations = vec![];
+    let mut quote = None;
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, c) in style.char_indices() {
+        match (quote, c) {
+            (_, '\\') => return None,
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.checked_sub(1)?,
+            (None, ';') if depth == 0 => {
+                declarations.push(&style[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    if quote.is_some() || depth > 0 {
+        return None;
+    }
+    declarations.push(&style[start..]);
+    declarations
+        .into_iter()
+        .filter(|declaration| !declaration.trim().is_empty())
+        .map(|declaration| {
+            let (property, value) = declaration.split_once(':')?;
+            Some((
+                property.trim().to_string(),
+                encode_double_quoted_attribute(value.trim()).to_string(),
+            ))
+        })
+        .collect()
+}
+
+fn convert(node: NodeRef<scraper::Node>, raw_text: bool) -> Option<Node> {
+    match node.value() {
+        scraper::Node::Text(text) => Some(text_node(if raw_text {
+            text.to_string()
+        } else {
+            encode_text(&**text).to_string()
+        })),
+        scraper::Node::Comment(comment) => Some(Node {
+            node_type: NodeType::Comment(""),
+            text: Some(comment.to_string()),
+            ..Node::default()
+        }),
+        scraper::Node::Element(element) => {
+            let mut converted = Node {
+                node_type: match intern_tag(element.name()) {
+                    Some(tag) if VOID_ELEMENTS.contains(&tag) => NodeType::SelfClosing(tag),
+                    Some(tag) => NodeType::Normal(tag),
+                    None => NodeType::Element(element.name().to_string()),
+                },
+                ..Node::default()
+            };
+            for (name, value) in element.attrs() {
+                match name {
+                    "id" => {
+                        converted.html_id = Some(encode_double_quoted_attribute(value).to_string())
+                    }
+                    "class" => converted
+                        .class_list
+                        .extend(value.split_whitespace().map(|class| class.to_string())),
+                    "style" => match style_declarations(value) {
+                        Some(declarations) => converted.node_style.extend(declarations),
+                        None => {
+                            converted.attributes.insert(
+                                name.to_string(),
+                                encode_double_quoted_attribute(value).to_string(),
+                            );
+                        }
+                    },
+                    _ => {
+                        converted.attributes.insert(
+                            name.to_string(),
+                            encode_double_quoted_attribute(value).to_string(),
+                        );
+                    }
+                }
+            }
+            let raw_text = RAW_TEXT_ELEMENTS.contains(&element.name());
+            convert_children(node, &mut converted, raw_text);
+            Some(converted)
+        }
+        _ => None,
+    }
+}
+
+/// The leading text goes in `Node::text`, rendered before the children, other texts become text nodes
+fn convert_children(node: NodeRef<scraper::Node>, parent: &mut Node, raw_text: bool) {
+    for child in node.children().filter_map(|child| convert(child, raw_text)) {
+        let is_text = matches!(child.node_type, NodeType::Fragment) && child.children.is_empty();
+        if is_text && parent.children.is_empty() {
+            parent
+                .text
+                .get_or_insert_with(String::new)
+                .push_str(child.text.as_deref().unwrap_or_default());
+        } else {
+            parent.children.push(child);
+        }
+    }
+}
+
+impl Node {
+    /// Parse an HTML fragment into a node tree.
+    ///
+    /// Returns the element itself when the fragment has a single root, a [`NodeType::Fragment`] otherwise.
+    /// Texts are kept escaped, so rendering the tree gives back equivalent HTML.
+    /// Every element is kept, custom and legacy ones (`<center>`) as [`NodeType::Element`].
+    /// Styles that can't be split in declarations, like `url(data:…)` values, are kept as is.
+    /// ```rust,no_run
+    /// # use viewy::prelude::*;
+    /// let legacy = Node::from_html(r#"<nav class="legacy-nav"><a href="/">Home</a></nav>"#);
+    /// assert_eq!(legacy.find_by_tag("a").count(), 1);
+    /// ```
+    pub fn from_html(html: &str) -> Node {
+        let document = Html::parse_fragment(html);
+        let mut fragment = Node {
+            node_type: NodeType::Fragment,
+            ..Node::default()
+        };
+        convert_children(*document.root_element(), &mut fragment, false);
+
+        if fragment.text.is_none() && fragment.children.len() == 1 {
+            fragment.children.remove(0)
+        } else {
+            fragment
+        }
+    }
+
+    /// Compare two trees ignoring node identifiers and widget origins, for golden tests.
+    pub fn structure_eq(&self, other: &Node) -> bool {
+        self.node_type == other.node_type
+            && self.text == other.text
+            && self.html_id == other.html_id
+            && self.class_list == other.class_list
+            && self.node_style == other.node_style
+            && self.attributes == other.attributes
+            && self.children.len() == other.children.len()
+            && self
+                .children
+                .iter()
+                .zip(&other.children)
+                .all(|(child, other_child)| child.structure_eq(other_child))
+    }
+}
@@ -1,19 +1,158 @@
+use std::any::{Any, TypeId, type_name};
+use std::collections::HashMap;
+
+use percent_encoding::percent_decode_str;
+
+use crate::core::theme::Theme;
 use crate::node::Node;
 
-/// `Layout` is a type alias for a function that takes a `Node` and returns a `Node`.
-/// This function will be used to transform the content of a Page.
+/// A `Layout` wraps the content of a Page, with access to the [`PageContext`] of the rendered page.
+///
+/// Any `Fn(Node) -> Node` that doesn't borrow is a layout, implement the trait to use the page context.
+/// Pages accept a chain of layouts, each one wrapping the result of the next one.
 ///
 /// # Example
-/// A layout function might be used to wrap page content within a common site layout.
+/// A root shell plus a section sidebar highlighting the current page.
 ///
-/// ```rust
+/// ```rust,no_run
 /// use viewy::prelude::*;
-/// fn layout(content: Node) -> Node {
-///    // Components that compose the layout
+///
+/// fn shell(content: Node) -> Node {
+///    // Components that compose the root layout
+/// #  content
+/// }
+///
+/// struct SettingsSidebar;
+///
+/// impl Layout for SettingsSidebar {
+///     fn render(&self, content: Node, context: &PageContext) -> Node {
+///         let mut sidebar = VStack::new(Alignment::Stretch);
+///         for (label, href) in [("Profile", "/settings/profile"), ("Security", "/settings/security")] {
+///             let mut button = Button::new(label, ButtonStyle::Flat);
+///             if context.is_active(href) {
+///                 button.set_attr("aria-current", "page");
+///             }
+///             sidebar.append_child(button);
+///         }
+///         HStack::new(Alignment::Start)
+///             .append_child(sidebar)
+///             .append_child(content)
+///             .into()
+///     }
 /// }
 ///
-/// let page = Page::with_title("Page Title")
-///     .with_content(View::new())
-///     .with_layout(&layout);
+/// let page = Page::with_title("Profile")
+///     .with_layout(shell)
+///     .with_layout(SettingsSidebar)
+///     .with_content(View::new());
 /// ```
-pub type Layout<'a> = &'a dyn Fn(Node) -> Node;
+pub trait Layout: Send + Sync {
+    fn render(&self, content: Node, context: &PageContext) -> Node;
+
+    /// Identifies the layout for boosted navigation, pages are only swapped in place between identical layout chains.
+    ///
+    /// Defaults to the type name, include any state rendered by the layout
+    /// (like the active navigation item) to reload the layout when it changes.
+    fn id(&self) -> String {
+        type_name::<Self>().to_string()
+    }
+}
+
+impl<F> Layout for F
+where
+    F: Fn(Node) -> Node + Send + Sync + 'static,
+{
+    fn render(&self, content: Node, _context: &PageContext) -> Node {
+        self(content)
+    }
+
+    /// Closures defined in the same function share their type name, the type id tells them apart
+    fn id(&self) -> String {
+        format!("{}#{:?}", type_name::<F>(), TypeId::of::<F>())
+    }
+}
+
+/// Typed values attached to a page and read by its layouts, like the signed in user.
+#[derive(Default)]
+pub struct PageExtensions(HashMap<TypeId, Box<dyn Any + Send + Sync>>);
+
+impl PageExtensions {
+    pub fn insert<T>(&mut self, value: T)
+    where
+        T: Any + Send + Sync,
+    {
+        self.0.insert(TypeId::of::<T>(), Box::new(value));
+    }
+
+    pub fn get<T>(&self) -> Option<&T>
+    where
+        T: Any + Send + Sync,
+    {
+        self.0
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+    }
+}
+
+/// Information about the rendered page given to its layouts
+pub struct PageContext {
+    pub(crate) title: String,
+    pub(crate) path: Option<String>,
+    pub(crate) theme: Theme,
+    pub(crate) extensions: PageExtensions,
+}
+
+impl PageContext {
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Path of the current request, set by the http framework bindings or with `Page::with_path`
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn extension<T>(&self) -> Option<&T>
+    where
+        T: Any + Send + Sync,
+    {
+        self.extensions.get::<T>()
+    }
+
+    /// Whether `href` is the current page or one of its parents, to highlight the active navigation item
+    pub fn is_active(&self, href: &str) -> bool {
+        let Some(path) = self.path() else {
+            return false;
+        };
+        let href = href.trim_end_matches('/');
+        if href.is_empty() {
+            return path == "/";
+        }
+        path == href
+            || path
+                .strip_prefix(href)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+
+    /// Each parent path of the current page, with its last segment as label: `/settings/profile`
+    /// gives `[("settings", "/settings"), ("profile", "/settings/profile")]`. Labels are
+    /// percent-decoded, `/caf%C3%A9` gives `("café", "/caf%C3%A9")`.
+    pub fn breadcrumbs(&self) -> Vec<(String, String)> {
+        let mut href = String::new();
+        self.path()
+            .unwrap_or_default()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| {
+                href.push('/');
+                href.push_str(segment);
+                let label = percent_decode_str(segment).decode_utf8_lossy();
+                (label.into_owned(), href.clone())
+            })
+            .collect()
+    }
+}
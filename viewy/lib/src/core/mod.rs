@@ -1,9 +1,14 @@
+pub mod assets;
+pub mod cache;
 pub mod component;
 pub mod node;
 pub mod widget;
 
+mod html_parser;
+
 pub mod color;
 pub mod config;
+pub mod csrf;
 pub mod theme;
 
 pub mod layout;
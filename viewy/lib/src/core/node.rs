@@ -1,3 +1,5 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
 use short_uuid::ShortUuid;
 use std::collections::{BTreeMap, BTreeSet};
 use std::default::Default;
@@ -5,25 +7,229 @@ use std::fmt::Write;
 use std::hash::{Hash, Hasher};
 use uuid::Uuid;
 
-#[derive(Clone, Debug)]
+use crate::core::widget::{Widget, registered_widget};
+
+/// Version of the serialization schema of [`Node`], see [`VersionedNode`]
+pub const NODE_SCHEMA_VERSION: u32 = 1;
+
+/// HTML, SVG and MathML element names, resolved to a tag without allocating
+const KNOWN_TAGS: &str = "a abbr address area article aside audio b base bdi bdo blockquote body \
+    br button canvas caption cite code col colgroup data datalist dd del details dfn dialog div \
+    dl dt em embed fieldset figcaption figure footer form h1 h2 h3 h4 h5 h6 head header hgroup \
+    hr html i iframe img input ins kbd label legend li link main map mark menu meta meter nav \
+    noscript object ol optgroup option output p param picture pre progress q rp rt ruby s samp \
+    script search section select slot small source span strong style sub summary sup table tbody \
+    td template textarea tfoot th thead time title tr track u ul var video wbr svg circle \
+    clipPath defs desc ellipse filter foreignObject g image line linearGradient marker mask path \
+    pattern polygon polyline radialGradient rect stop symbol text textPath tspan use view math \
+    mfrac mi mn mo mover mroot mrow ms mspace msqrt msub msubsup msup mtable mtd mtext mtr \
+    munder munderover semantics annotation";
+
+/// Comment prefixes set by viewy, restored as is when deserializing
+const COMMENT_MARKERS: [&str; 2] = ["", "VIEWY_CONTENT"];
+
+/// Element names start with a letter, custom and legacy elements included
+fn is_element_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | ':'))
+}
+
+/// Tag of a known element named `name`, without allocating
+pub(crate) fn intern_tag(name: &str) -> Option<&'static str> {
+    KNOWN_TAGS.split_whitespace().find(|tag| *tag == name)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
 pub enum NodeType {
     Normal(&'static str),
     SelfClosing(&'static str),
+    /// Comment made of this static prefix followed by the node `text`
     Comment(&'static str),
+    /// Renders only its children, without any element around them
+    Fragment,
+    /// Element named at runtime, like the custom and legacy elements (`<center>`) of parsed HTML
+    Element(String),
+}
+
+impl NodeType {
+    /// Tag name of the element, `None` for comments and fragments
+    pub fn tag(&self) -> Option<&str> {
+        match self {
+            NodeType::Normal(tag) | NodeType::SelfClosing(tag) => Some(tag),
+            NodeType::Element(name) => Some(name),
+            NodeType::Comment(_) | NodeType::Fragment => None,
+        }
+    }
+}
+
+/// Named so that the serde derive doesn't tie the deserializer lifetime to `'static`
+type StaticStr = &'static str;
+
+/// Same schema as `NodeType`, deserialized before resolving the tag names
+#[derive(Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+enum OwnedNodeType {
+    Normal(String),
+    SelfClosing(String),
+    Comment(String),
+    Fragment,
+    Element(String),
+}
+
+/// Same schema as `Node`, converted once the tag names and the origin are checked
+#[derive(Deserialize)]
+struct SerializedNode {
+    #[serde(default = "Uuid::new_v4")]
+    identifier: Uuid,
+    #[serde(rename = "type")]
+    node_type: OwnedNodeType,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    children: Vec<Node>,
+    #[serde(default)]
+    html_id: Option<String>,
+    #[serde(default)]
+    class_list: BTreeSet<String>,
+    #[serde(default)]
+    node_style: BTreeMap<String, String>,
+    #[serde(default)]
+    attributes: BTreeMap<String, String>,
+    #[serde(default)]
+    origin: Option<String>,
+}
+
+impl TryFrom<SerializedNode> for Node {
+    type Error = String;
+
+    fn try_from(node: SerializedNode) -> Result<Self, Self::Error> {
+        let tag = |tag: String| intern_tag(&tag).ok_or_else(|| format!("unknown tag `{tag}`"));
+        let (node_type, text) = match node.node_type {
+            OwnedNodeType::Normal(name) => (NodeType::Normal(tag(name)?), node.text),
+            OwnedNodeType::SelfClosing(name) => (NodeType::SelfClosing(tag(name)?), node.text),
+            // Comment prefixes are static markers, other comments keep their text in `text`
+            OwnedNodeType::Comment(comment) => {
+                match COMMENT_MARKERS.iter().find(|marker| **marker == comment) {
+                    Some(marker) => (NodeType::Comment(marker), node.text),
+                    None => (
+                        NodeType::Comment(""),
+                        Some(comment + node.text.as_deref().unwrap_or_default()),
+                    ),
+                }
+            }
+            OwnedNodeType::Fragment => (NodeType::Fragment, node.text),
+            OwnedNodeType::Element(name) if is_element_name(&name) => {
+                (NodeType::Element(name), node.text)
+            }
+            OwnedNodeType::Element(name) => return Err(format!("invalid element name `{name}`")),
+        };
+        Ok(Node {
+            identifier: node.identifier,
+            node_type,
+            text,
+            children: node.children,
+            html_id: node.html_id,
+            class_list: node.class_list,
+            node_style: node.node_style,
+            attributes: node.attributes,
+            origin: node.origin.as_deref().and_then(registered_widget),
+        })
+    }
 }
 
 /// Represent HTML DOM node that will be generated on render
 /// Every widgets will configure one or many nodes.
-#[derive(Clone, Debug)]
+///
+/// Nodes serialize with serde, wrap the root in a [`VersionedNode`] to store or send a tree.
+/// Trees with tags other than the standard and custom elements (`my-element`) are rejected.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "SerializedNode")]
 pub struct Node {
+    #[serde(default = "Uuid::new_v4")]
     pub identifier: Uuid,
+    #[serde(rename = "type")]
     pub node_type: NodeType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub children: Vec<Node>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub html_id: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
     pub class_list: BTreeSet<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub node_style: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub attributes: BTreeMap<String, String>,
+    /// [`Widget::ORIGIN`] of the widget that produced this node, if any.
+    ///
+    /// Deserialized origins of widgets that are not linked in the binary are dropped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<StaticStr>,
+}
+
+/// A node tree tagged with the schema version it was serialized with.
+///
+/// Deserializing a tree written by a newer, unknown schema version fails instead of producing a partial tree.
+/// ```rust,no_run
+/// # use viewy::prelude::*;
+/// # fn store(node: Node) -> Result<Node, serde_json::Error> {
+/// let json = serde_json::to_string(&VersionedNode::from(node))?;
+/// let node = serde_json::from_str::<VersionedNode>(&json)?.into_node();
+/// # Ok(node)
+/// # }
+/// ```
+#[derive(Clone, Debug, Serialize)]
+pub struct VersionedNode {
+    version: u32,
+    node: Node,
+}
+
+impl VersionedNode {
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn into_node(self) -> Node {
+        self.node
+    }
+}
+
+impl From<Node> for VersionedNode {
+    fn from(node: Node) -> Self {
+        Self {
+            version: NODE_SCHEMA_VERSION,
+            node,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionedNode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Unchecked {
+            version: u32,
+            node: Node,
+        }
+
+        let unchecked = Unchecked::deserialize(deserializer)?;
+        if unchecked.version > NODE_SCHEMA_VERSION {
+            return Err(D::Error::custom(format!(
+                "unsupported node schema version {}, the latest known is {NODE_SCHEMA_VERSION}",
+                unchecked.version
+            )));
+        }
+        Ok(Self {
+            version: unchecked.version,
+            node: unchecked.node,
+        })
+    }
 }
 
 impl Eq for Node {}
@@ -51,13 +257,14 @@ impl Default for Node {
             node_style: BTreeMap::new(),
             attributes: BTreeMap::new(),
             html_id: None,
+            origin: None,
         }
     }
 }
 
 impl Node {
     pub fn get_node_style(&self) -> Option<(String, String)> {
-        if !self.node_style.is_empty() {
+        if !self.node_style.is_empty() && !matches!(self.node_type, NodeType::Fragment) {
             let short_identifier = ShortUuid::from_uuid(&self.identifier).to_string();
             let concat_property = self
                 .node_style
@@ -80,6 +287,15 @@ impl Node {
     }
 
     pub fn render(self, html_buffer: &mut String) {
+        if let NodeType::Fragment = self.node_type {
+            if let Some(text_content) = self.text {
+                html_buffer.push_str(&text_content);
+            }
+            for child in self.children {
+                child.render(html_buffer);
+            }
+            return;
+        }
         let mut attributes = self.attributes;
         if let Some(html_id) = self.html_id {
             attributes.insert("id".to_string(), html_id);
@@ -102,7 +318,8 @@ impl Node {
         }
 
         match &self.node_type {
-            NodeType::Normal(tag) => {
+            NodeType::Normal(_) | NodeType::Element(_) => {
+                let tag = self.node_type.tag().unwrap();
                 write!(html_buffer, "<{}", tag).unwrap();
                 for (k, v) in attributes {
                     write!(html_buffer, r#" {}="{}""#, k, v).unwrap();
@@ -124,8 +341,125 @@ impl Node {
                 write!(html_buffer, "/>").unwrap();
             }
             NodeType::Comment(comment) => {
-                write!(html_buffer, "<!--{comment}-->").unwrap();
+                write!(
+                    html_buffer,
+                    "<!--{comment}{}-->",
+                    self.text.unwrap_or_default()
+                )
+                .unwrap();
+            }
+            NodeType::Fragment => unreachable!("fragments are rendered without element"),
+        }
+    }
+}
+
+/// Depth-first (pre-order) iterator over a node and all its descendants, see [`Node::iter`]
+pub struct NodeIter<'a> {
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for NodeIter<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.children.iter().rev());
+        Some(node)
+    }
+}
+
+/// Tree queries and transforms, to audit or rewrite the content before rendering.
+///
+/// ```rust,no_run
+/// # use viewy::prelude::*;
+/// # let mut page_content: Node = View::new().into();
+/// // Add `rel=noopener` to all external links
+/// page_content.transform(
+///     |node| node.tag() == Some("a") && node.attributes.get("href").is_some_and(|href| href.starts_with("http")),
+///     |link| {
+///         link.attributes.insert("rel".to_string(), "noopener".to_string());
+///     },
+/// );
+/// ```
+impl Node {
+    /// Tag name of the rendered element, `None` for comments and fragments
+    pub fn tag(&self) -> Option<&str> {
+        self.node_type.tag()
+    }
+
+    /// Iterate over this node and all its descendants, depth-first
+    pub fn iter(&self) -> NodeIter<'_> {
+        NodeIter { stack: vec![self] }
+    }
+
+    pub fn find_all<'a, P>(&'a self, predicate: P) -> impl Iterator<Item = &'a Node>
+    where
+        P: Fn(&Node) -> bool + 'a,
+    {
+        self.iter().filter(move |node| predicate(node))
+    }
+
+    /// First node matching `predicate`, depth-first
+    pub fn find<P>(&self, predicate: P) -> Option<&Node>
+    where
+        P: Fn(&Node) -> bool,
+    {
+        self.iter().find(|node| predicate(node))
+    }
+
+    pub fn find_by_class<'a>(&'a self, class: &'a str) -> impl Iterator<Item = &'a Node> {
+        self.find_all(move |node| node.class_list.contains(class))
+    }
+
+    /// Nodes having the attribute `name`, with the given `value` if any
+    pub fn find_by_attribute<'a>(
+        &'a self,
+        name: &'a str,
+        value: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a Node> {
+        self.find_all(move |node| {
+            node.attributes
+                .get(name)
+                .is_some_and(|attribute| value.is_none_or(|value| attribute == value))
+        })
+    }
+
+    pub fn find_by_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a Node> {
+        self.find_all(move |node| node.tag() == Some(tag))
+    }
+
+    /// Nodes produced by the widget `W`
+    pub fn find_by_widget<W>(&self) -> impl Iterator<Item = &Node>
+    where
+        W: Widget,
+    {
+        self.find_all(|node| node.origin == Some(W::ORIGIN))
+    }
+
+    /// Call `visitor` on this node and all its descendants, depth-first
+    pub fn walk_mut<F>(&mut self, mut visitor: F)
+    where
+        F: FnMut(&mut Node),
+    {
+        fn walk<F: FnMut(&mut Node)>(node: &mut Node, visitor: &mut F) {
+            visitor(node);
+            for child in node.children.iter_mut() {
+                walk(child, visitor);
             }
         }
+        walk(self, &mut visitor);
+    }
+
+    /// Apply `transform` to every node matching `predicate`
+    pub fn transform<P, F>(&mut self, predicate: P, mut transform: F)
+    where
+        P: Fn(&Node) -> bool,
+        F: FnMut(&mut Node),
+    {
+        self.walk_mut(|node| {
+            if predicate(node) {
+                transform(node);
+            }
+        });
     }
 }
@@ -0,0 +1,369 @@
+use std::fmt::{Display, Formatter};
+
+use html_escape::{encode_single_quoted_attribute, encode_text};
+
+use crate::core::config::Config;
+use crate::core::theme::{Color, Theme, high_contrast_color};
+
+/// Text direction of the document, set on the `<html>` element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextDirection {
+    Ltr,
+    Rtl,
+    Auto,
+}
+
+impl TextDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TextDirection::Ltr => "ltr",
+            TextDirection::Rtl => "rtl",
+            TextDirection::Auto => "auto",
+        }
+    }
+}
+
+/// Content of the `robots` meta tag, telling search engines whether to index the page and follow its links.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Robots {
+    pub index: bool,
+    pub follow: bool,
+}
+
+impl Robots {
+    /// Keep the page out of search engines
+    pub fn none() -> Self {
+        Self {
+            index: false,
+            follow: false,
+        }
+    }
+}
+
+impl Default for Robots {
+    fn default() -> Self {
+        Self {
+            index: true,
+            follow: true,
+        }
+    }
+}
+
+impl Display for Robots {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}, {}",
+            if self.index { "index" } else { "noindex" },
+            if self.follow { "follow" } else { "nofollow" }
+        )
+    }
+}
+
+/// Content of the `viewport` meta tag.
+///
+/// Defaults to `width=device-width, initial-scale=1.0`, zooming is allowed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Viewport {
+    width: String,
+    initial_scale: f32,
+    maximum_scale: Option<f32>,
+    user_scalable: bool,
+    viewport_fit_cover: bool,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self {
+            width: "device-width".to_string(),
+            initial_scale: 1.0,
+            maximum_scale: None,
+            user_scalable: true,
+            viewport_fit_cover: false,
+        }
+    }
+}
+
+impl Viewport {
+    pub fn width(mut self, width: &str) -> Self {
+        self.width = width.to_string();
+        self
+    }
+
+    pub fn initial_scale(mut self, initial_scale: f32) -> Self {
+        self.initial_scale = initial_scale;
+        self
+    }
+
+    pub fn maximum_scale(mut self, maximum_scale: f32) -> Self {
+        self.maximum_scale = Some(maximum_scale);
+        self
+    }
+
+    /// Prevent the user from zooming. Avoid it on content pages, it hurts accessibility.
+    pub fn user_scalable(mut self, user_scalable: bool) -> Self {
+        self.user_scalable = user_scalable;
+        self
+    }
+
+    /// Extend the page under the notch and rounded corners of the device (`viewport-fit=cover`)
+    pub fn cover(mut self) -> Self {
+        self.viewport_fit_cover = true;
+        self
+    }
+}
+
+impl Display for Viewport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Debug formatting keeps every digit of the scales while still writing `1.0` for `1.0`
+        write!(
+            f,
+            "width={}, initial-scale={:?}",
+            self.width, self.initial_scale
+        )?;
+        if let Some(maximum_scale) = self.maximum_scale {
+            write!(f, ", maximum-scale={maximum_scale:?}")?;
+        }
+        if !self.user_scalable {
+            write!(f, ", user-scalable=no")?;
+        }
+        if self.viewport_fit_cover {
+            write!(f, ", viewport-fit=cover")?;
+        }
+        Ok(())
+    }
+}
+
+/// Open Graph metadata used by social networks and messaging apps to build link previews.
+///
+/// Title, description and url default to the ones of the page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenGraph {
+    kind: String,
+    image: Option<String>,
+    image_alt: Option<String>,
+    site_name: Option<String>,
+    locale: Option<String>,
+}
+
+impl Default for OpenGraph {
+    fn default() -> Self {
+        Self {
+            kind: "website".to_string(),
+            image: None,
+            image_alt: None,
+            site_name: None,
+            locale: None,
+        }
+    }
+}
+
+impl OpenGraph {
+    /// `og:type`, defaults to `website`
+    pub fn kind(mut self, kind: &str) -> Self {
+        self.kind = kind.to_string();
+        self
+    }
+
+    /// Absolute url of the preview image
+    pub fn image(mut self, url: &str, alt: &str) -> Self {
+        self.image = Some(url.to_string());
+        self.image_alt = Some(alt.to_string());
+        self
+    }
+
+    pub fn site_name(mut self, site_name: &str) -> Self {
+        self.site_name = Some(site_name.to_string());
+        self
+    }
+
+    /// `og:locale`, like `fr_FR`
+    pub fn locale(mut self, locale: &str) -> Self {
+        self.locale = Some(locale.to_string());
+        self
+    }
+}
+
+/// Twitter (X) card displayed when the page is shared.
+///
+/// Title, description and image are taken from the page and its [`OpenGraph`] metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TwitterCard {
+    Summary { site: Option<String> },
+    SummaryLargeImage { site: Option<String> },
+}
+
+impl TwitterCard {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TwitterCard::Summary { .. } => "summary",
+            TwitterCard::SummaryLargeImage { .. } => "summary_large_image",
+        }
+    }
+
+    fn site(&self) -> Option<&String> {
+        match self {
+            TwitterCard::Summary { site } | TwitterCard::SummaryLargeImage { site } => {
+                site.as_ref()
+            }
+        }
+    }
+}
+
+/// Per page metadata rendered in the document `<head>`, set with the `Page` builder methods.
+#[derive(Debug, Clone, Default)]
+pub struct PageHead {
+    pub(crate) description: Option<String>,
+    pub(crate) canonical_url: Option<String>,
+    pub(crate) open_graph: Option<OpenGraph>,
+    pub(crate) twitter_card: Option<TwitterCard>,
+    pub(crate) robots: Option<Robots>,
+    pub(crate) lang: Option<String>,
+    pub(crate) dir: Option<TextDirection>,
+    pub(crate) viewport: Viewport,
+    pub(crate) stylesheets: Vec<String>,
+    pub(crate) module_scripts: Vec<String>,
+}
+
+fn meta(buffer: &mut String, attribute: &str, key: &str, content: &str) {
+    buffer.push_str(&format!(
+        "<meta {attribute}='{key}' content='{}'>",
+        encode_single_quoted_attribute(content)
+    ));
+}
+
+impl PageHead {
+    /// Attributes of the `<html>` element
+    pub(crate) fn html_attributes(&self) -> String {
+        let mut attributes = String::new();
+        if let Some(lang) = &self.lang {
+            attributes.push_str(&format!(" lang='{}'", encode_single_quoted_attribute(lang)));
+        }
+        if let Some(dir) = &self.dir {
+            attributes.push_str(&format!(" dir='{}'", dir.as_str()));
+        }
+        attributes
+    }
+
+    /// Title, SEO and link preview tags
+    pub(crate) fn render_metadata(&self, title: &str, config: &Config, theme: &Theme) -> String {
+        let mut buffer = format!("<title>{}</title>", encode_text(title));
+        meta(&mut buffer, "name", "viewport", &self.viewport.to_string());
+        // Read before the stylesheet is loaded, avoids a flash of the other color scheme
+        let color_scheme = match theme {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::Auto | Theme::HighContrast => "light dark",
+        };
+        meta(&mut buffer, "name", "color-scheme", color_scheme);
+
+        // Colors of every theme, the theme switcher script picks the one of the theme it applies
+        for (scheme, variant) in [("light", Theme::Light), ("dark", Theme::Dark)] {
+            let accent = match variant {
+                Theme::Dark => &config.colors.accent.dark,
+                _ => &config.colors.accent.light,
+            };
+            let colors = [
+                (Theme::Light, config.colors.accent.light.to_string()),
+                (Theme::Dark, config.colors.accent.dark.to_string()),
+                (Theme::Auto, accent.to_string()),
+                (
+                    Theme::HighContrast,
+                    high_contrast_color(config, Color::Accent, &variant).to_string(),
+                ),
+            ];
+            let content = colors
+                .iter()
+                .find(|(color_theme, _)| color_theme == theme)
+                .map(|(_, color)| color.as_str())
+                .unwrap_or_default();
+            buffer.push_str(&format!(
+                "<meta name='theme-color' media='(prefers-color-scheme: {scheme})' content='{content}'"
+            ));
+            for (color_theme, color) in &colors {
+                buffer.push_str(&format!(" data-v-{}='{color}'", color_theme.as_str()));
+            }
+            buffer.push('>');
+        }
+
+        if let Some(description) = &self.description {
+            meta(&mut buffer, "name", "description", description);
+        }
+        if let Some(robots) = &self.robots {
+            meta(&mut buffer, "name", "robots", &robots.to_string());
+        }
+        if let Some(canonical_url) = &self.canonical_url {
+            buffer.push_str(&format!(
+                "<link rel='canonical' href='{}'>",
+                encode_single_quoted_attribute(canonical_url)
+            ));
+        }
+
+        if let Some(open_graph) = &self.open_graph {
+            meta(&mut buffer, "property", "og:type", &open_graph.kind);
+            meta(&mut buffer, "property", "og:title", title);
+            if let Some(description) = &self.description {
+                meta(&mut buffer, "property", "og:description", description);
+            }
+            if let Some(canonical_url) = &self.canonical_url {
+                meta(&mut buffer, "property", "og:url", canonical_url);
+            }
+            if let Some(image) = &open_graph.image {
+                meta(&mut buffer, "property", "og:image", image);
+            }
+            if let Some(image_alt) = &open_graph.image_alt {
+                meta(&mut buffer, "property", "og:image:alt", image_alt);
+            }
+            if let Some(site_name) = &open_graph.site_name {
+                meta(&mut buffer, "property", "og:site_name", site_name);
+            }
+            if let Some(locale) = &open_graph.locale {
+                meta(&mut buffer, "property", "og:locale", locale);
+            }
+        }
+
+        if let Some(twitter_card) = &self.twitter_card {
+            meta(&mut buffer, "name", "twitter:card", twitter_card.as_str());
+            if let Some(site) = twitter_card.site() {
+                meta(&mut buffer, "name", "twitter:site", site);
+            }
+            meta(&mut buffer, "name", "twitter:title", title);
+            if let Some(description) = &self.description {
+                meta(&mut buffer, "name", "twitter:description", description);
+            }
+            if let Some(open_graph) = &self.open_graph {
+                if let Some(image) = &open_graph.image {
+                    meta(&mut buffer, "name", "twitter:image", image);
+                }
+                if let Some(image_alt) = &open_graph.image_alt {
+                    meta(&mut buffer, "name", "twitter:image:alt", image_alt);
+                }
+            }
+        }
+        buffer
+    }
+
+    /// Per page stylesheets and module scripts, prefixed by `base_url` when relative to the site root
+    pub(crate) fn render_assets(&self, base_url: &str) -> String {
+        let resolve = |url: &str| {
+            if url.starts_with('/') && !url.starts_with("//") {
+                format!("{base_url}{url}")
+            } else {
+                url.to_string()
+            }
+        };
+        let stylesheets = self.stylesheets.iter().map(|href| {
+            format!(
+                "<link href='{}' rel='stylesheet'>",
+                encode_single_quoted_attribute(&resolve(href))
+            )
+        });
+        let module_scripts = self.module_scripts.iter().map(|src| {
+            format!(
+                "<script type='module' src='{}'></script>",
+                encode_single_quoted_attribute(&resolve(src))
+            )
+        });
+        stylesheets.chain(module_scripts).collect()
+    }
+}
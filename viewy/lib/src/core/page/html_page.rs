@@ -1,18 +1,41 @@
+use crate::core::assets::{static_asset_url, widget_script_url};
 use crate::core::config::Config;
+use crate::core::csrf::CsrfToken;
+use crate::core::page::head::PageHead;
+use crate::core::theme::Theme;
 use std::env;
 
+/// Per page elements of the document head
+pub struct HtmlHead<'a> {
+    pub page_head: &'a PageHead,
+    pub csrf_token: Option<&'a CsrfToken>,
+    /// Set when boosted navigation is enabled
+    pub boosted_layout_id: Option<&'a str>,
+    /// Load the runtime swapping in the deferred components
+    pub streamed: bool,
+    /// Scripts of the widgets on the page, preloaded
+    pub script_ids: &'a [String],
+    /// Styles of the widgets on the page, inlined when set
+    pub critical_css: Option<&'a str>,
+    /// Application stylesheet, or the stylesheet of the page config
+    pub stylesheet_url: &'a str,
+}
+
+/// Prefix of the asset urls, from the `BASE_URL` environment variable
+pub fn base_url() -> String {
+    env::var("BASE_URL").unwrap_or_default()
+}
+
 pub fn get_full_html_page(
     config: &Config,
     title: String,
     body_prefix: String,
     content: String,
-    theme_variant: String,
+    theme: &Theme,
+    head: HtmlHead,
     insert_base_element: bool,
 ) -> String {
-    let base_url = match env::var("BASE_URL") {
-        Ok(url) => url,
-        Err(_) => "".to_string(),
-    };
+    let base_url = base_url();
     let base_elem = {
         if insert_base_element {
             format!("<base href='{}/'>", base_url)
@@ -34,35 +57,87 @@ pub fn get_full_html_page(
         })
         .collect::<Vec<String>>()
         .join("");
+    let csrf_meta = head
+        .csrf_token
+        .map(|token| format!("<meta name='csrf-token' content='{}'>", token.as_str()))
+        .unwrap_or_default();
+    let (boost_meta, body_boost) = head
+        .boosted_layout_id
+        .map(|layout_id| {
+            (
+                format!("<meta name='viewy-layout' content='{layout_id}'>"),
+                " data-v-boost".to_string(),
+            )
+        })
+        .unwrap_or_default();
+    let streaming_runtime = if head.streamed {
+        format!(
+            "<script src='{base_url}{}'></script>",
+            static_asset_url("js/streaming.js")
+        )
+    } else {
+        String::new()
+    };
+    let script_preloads = head
+        .script_ids
+        .iter()
+        .map(|script_id| {
+            format!(
+                "<link rel='modulepreload' href='{base_url}{}'>",
+                widget_script_url(script_id)
+            )
+        })
+        .collect::<String>();
+    let app_stylesheet_url = head.stylesheet_url;
+    let app_stylesheet = match head.critical_css {
+        Some(critical_css) => format!(
+            "<style>{critical_css}</style>\
+            <link rel='preload' href='{base_url}{app_stylesheet_url}' as='style' onload=\"this.onload=null;this.rel='stylesheet'\">\
+            <noscript><link href='{base_url}{app_stylesheet_url}' rel='stylesheet'></noscript>"
+        ),
+        None => format!("<link href='{base_url}{app_stylesheet_url}' rel='stylesheet'>"),
+    };
+    let importmap_url = static_asset_url("js/importmap.js");
+    let static_stylesheet_url = static_asset_url("css/static.css");
+    let app_script_url = static_asset_url("js/app.js");
+    let html_attributes = head.page_head.html_attributes();
+    let metadata = head.page_head.render_metadata(&title, config, theme);
+    let assets = head.page_head.render_assets(&base_url);
     format!(
         r"
         <!doctype html>
-        <html>
+        <html{html_attributes} class='app-theme--{theme_variant}'>
             <head>
                 <meta charset='utf-8' />
-                <title>{title}</title>
-                <script type='text/javascript' src='{base_elem}/viewy-static/js/importmap.js'></script>
+                {metadata}
+                <script type='text/javascript' src='{base_url}{importmap_url}'></script>
                 <link rel='preconnect' href='{base_url}'>
                 {base_elem}
                 {favicons}
-                <link href='{base_url}/viewy-static/app.css' rel='stylesheet'>
-                <link href='{base_url}/viewy-static/css/static.css' rel='stylesheet'>
-                <script type='module' src='{base_url}/viewy-static/js/app.js'></script>
-                <meta name='viewport' content='width=device-width, initial-scale=1.0, user-scalable=no'>
+                {app_stylesheet}
+                <link href='{base_url}{static_stylesheet_url}' rel='stylesheet'>
+                <script type='module' src='{base_url}{app_script_url}'></script>
+                {assets}
+                {script_preloads}
                 <meta name='apple-mobile-web-app-capable' content='yes'>
+                {csrf_meta}
+                {boost_meta}
+                {streaming_runtime}
             </head>
-            <body class='app-theme--{theme_variant}'>
+            <body class='app-theme--{theme_variant}'{body_boost}>
                 {body_prefix}
                 {content}
             </body>
         </html>
     ",
-        title = title,
         body_prefix = body_prefix,
         content = content,
-        theme_variant = theme_variant,
+        theme_variant = theme.as_str(),
         base_elem = base_elem,
         favicons = favicons,
         base_url = base_url,
+        csrf_meta = csrf_meta,
+        boost_meta = boost_meta,
+        body_boost = body_boost,
     )
 }
@@ -1,52 +1,197 @@
 use uuid::Uuid;
 
+use crate::CONFIG;
+use crate::core::assets::{
+    app_stylesheet_url, config_stylesheet, register_config_stylesheet, widget_script_url,
+};
+use crate::core::component::AsyncComponent;
 use crate::core::config::Config;
-use crate::core::layout::Layout;
-use crate::core::page::html_page::get_full_html_page;
-use crate::core::theme::Theme;
+use crate::core::csrf::CsrfToken;
+use crate::core::layout::{Layout, PageContext, PageExtensions};
+use crate::core::page::html_page::{HtmlHead, base_url, get_full_html_page};
+use crate::core::theme::{Theme, get_critical_stylesheet_from, get_widgets_stylesheet_from};
+use crate::core::widget::SCRIPT_ATTRIBUTE;
 use crate::node::{Node, NodeType};
 use crate::widgets::icon::icons::sprite_from_icon_ids;
-use futures::Stream;
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::{Stream, StreamExt};
+use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Write;
+use std::hash::{Hash, Hasher};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::vec;
 
+mod head;
 mod html_page;
 
+use head::PageHead;
+pub use head::{OpenGraph, Robots, TextDirection, TwitterCard, Viewport};
+
 pub trait HtmlStream: Stream<Item = String> + Send {}
 impl<T> HtmlStream for T where T: Stream<Item = String> + Send {}
 
 /// `RenderMode` enum is used to determine how to render a `Page`.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum RenderMode {
     /// This mode will result in a complete HTML page, with the page content wrapped within the page's `Layout`.
     Complete,
     /// This mode will just render the page content without any layout.
     ContentOnly,
-    /// Not used for the moment
+    /// This mode will render the layout with an empty content region, marked by a `VIEWY_CONTENT` comment.
     LayoutOnly,
 }
 
+/// Percent-encode a header value so that it can be read back with `decodeURIComponent`
+pub(crate) fn encode_header_value(value: &str) -> String {
+    value.bytes().fold(String::new(), |mut encoded, byte| {
+        if byte.is_ascii_alphanumeric() || b"-_.~".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            write!(encoded, "%{byte:02X}").unwrap();
+        }
+        encoded
+    })
+}
+
+/// Attribute of the element wrapping the page content inside the layout, used by boosted navigation.
+pub const CONTENT_REGION_ATTRIBUTE: &str = "data-v-page-content";
+
+type NodeTransform<'a> = Box<dyn Fn(&mut Node) + Send + Sync + 'a>;
+
+/// Async component waiting to be streamed, identified by the number of its placeholder
+struct DeferredComponent {
+    id: usize,
+    render: BoxFuture<'static, Node>,
+}
+
+/// Widgets and scripts already in the page of a stream, filled by `Page::render` with those of
+/// the shell, then with those of each resolved component
+#[derive(Default)]
+struct StreamedAssets {
+    widgets: Vec<&'static str>,
+    script_ids: Vec<String>,
+    /// Config of the page when its critical CSS is inlined, `Viewy.toml` if the page has none
+    critical_css: Option<Option<Config>>,
+}
+
+/// Styles and script preloads of the widgets of `content` missing from the page so far
+fn resolved_assets(content: &Node, assets: &mut StreamedAssets) -> String {
+    let widgets: Vec<&'static str> = collect_used_widgets(content)
+        .into_iter()
+        .filter(|widget| !assets.widgets.contains(widget))
+        .collect();
+    let mut html = match &assets.critical_css {
+        Some(config) if !widgets.is_empty() => format!(
+            "<style>{}</style>",
+            get_widgets_stylesheet_from(
+                config.as_ref().unwrap_or(&CONFIG),
+                widgets.iter().copied()
+            )
+        ),
+        _ => String::new(),
+    };
+    assets.widgets.extend(widgets);
+    for script_id in collect_used_script_ids(content) {
+        if !assets.script_ids.contains(&script_id) {
+            html.push_str(&format!(
+                "<link rel='modulepreload' href='{}{}'>",
+                base_url(),
+                widget_script_url(&script_id)
+            ));
+            assets.script_ids.push(script_id);
+        }
+    }
+    html
+}
+
+/// Chunk replacing the placeholder of a deferred component once it is rendered.
+///
+/// Complete pages swap it in with an inline call to the streaming runtime, injected content is resolved by `inject_content`.
+fn resolved_chunk(
+    id: usize,
+    content: Node,
+    render_mode: &RenderMode,
+    assets: &mut StreamedAssets,
+) -> String {
+    let mut icon_ids = vec![];
+    collect_used_icon_ids(&content, &mut icon_ids);
+    let sprite = sprite_from_icon_ids(icon_ids.iter().map(|id| id.as_str()));
+    let head = match render_mode {
+        RenderMode::Complete => resolved_assets(&content, assets),
+        _ => String::new(),
+    };
+    let mut html = String::new();
+    content.render(&mut html);
+    let mut chunk = format!("{head}<template data-v-resolved='{id}'>{sprite}{html}</template>");
+    if matches!(render_mode, RenderMode::Complete) {
+        chunk.push_str(&format!("<script>viewyResolve('{id}')</script>"));
+    }
+    chunk
+}
+
 pub struct Page<'a> {
     pub content: Node,
     title: String,
-    config: Config,
+    /// Set with [`Page::with_config`], `Viewy.toml` is used otherwise
+    config: Option<Config>,
     theme: Theme,
-    layout: Layout<'a>,
+    head: PageHead,
+    layouts: Vec<Box<dyn Layout + 'a>>,
+    transforms: Vec<NodeTransform<'a>>,
+    path: Option<String>,
+    extensions: PageExtensions,
+    boosted_navigation: bool,
+    deferred: Vec<DeferredComponent>,
+    critical_css: bool,
+    pub(crate) csrf_token: Option<CsrfToken>,
 }
 
-const fn default_layout(content: Node) -> Node {
-    content
+fn content_region(content: Node, boosted_navigation: bool) -> Node {
+    if !boosted_navigation {
+        return content;
+    }
+    let mut region = Node::default();
+    region
+        .attributes
+        .insert(CONTENT_REGION_ATTRIBUTE.to_string(), String::new());
+    region.children.push(content);
+    region
 }
 
-fn collect_used_icon_ids(node: &Node, icon_ids: &mut Vec<String>) {
-    if let Some(icon_id) = node.attributes.get("data-v-icon-id") {
-        icon_ids.push(icon_id.clone());
+/// Ids of the scripts of the widgets in the tree, without duplicates
+fn collect_used_script_ids(node: &Node) -> Vec<String> {
+    let mut script_ids = vec![];
+    for script_id in node
+        .iter()
+        .filter_map(|node| node.attributes.get(SCRIPT_ATTRIBUTE))
+    {
+        if !script_ids.contains(script_id) {
+            script_ids.push(script_id.clone());
+        }
     }
+    script_ids
+}
 
-    for child in &node.children {
-        collect_used_icon_ids(child, icon_ids);
+/// Origins of the widgets in the tree, without duplicates
+fn collect_used_widgets(node: &Node) -> Vec<&'static str> {
+    let mut widgets = vec![];
+    for widget in node.iter().filter_map(|node| node.origin) {
+        if !widgets.contains(&widget) {
+            widgets.push(widget);
+        }
     }
+    widgets
+}
+
+fn collect_used_icon_ids(node: &Node, icon_ids: &mut Vec<String>) {
+    icon_ids.extend(
+        node.iter()
+            .filter_map(|node| node.attributes.get("data-v-icon-id"))
+            .cloned(),
+    );
 }
 
 impl<'a> Page<'a> {
@@ -54,19 +199,89 @@ impl<'a> Page<'a> {
         Self {
             content: Default::default(),
             title: title.to_string(),
-            config: Config::default(),
+            config: None,
             theme: Theme::Auto,
-            layout: &default_layout,
+            head: PageHead::default(),
+            layouts: vec![],
+            transforms: vec![],
+            path: None,
+            extensions: PageExtensions::default(),
+            boosted_navigation: false,
+            deferred: vec![],
+            critical_css: false,
+            csrf_token: None,
         }
     }
+    /// Render the page with the app settings and theme of `config`, its palette and shapes are
+    /// compiled in a stylesheet of their own by
+    /// [`register_config_stylesheet`](crate::prelude::register_config_stylesheet), to call at startup.
+    ///
+    /// A config that was not registered is registered by the first page rendered with it, which
+    /// then waits for its stylesheet to compile. `Config` is `Clone`, keep each tenant config and
+    /// clone it for its pages.
     pub fn with_config(mut self, config: Config) -> Self {
-        self.config = config;
+        self.config = Some(config);
         self
     }
+    /// Theme of the page, the bindings replace [`Theme::Auto`] by the theme picked by the user with a
+    /// [`ThemeSwitcher`](crate::widgets::theme_switcher::ThemeSwitcher)
     pub fn with_theme(mut self, theme: Theme) -> Self {
         self.theme = theme;
         self
     }
+    /// Summary of the page shown by search engines and link previews
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.head.description = Some(description.to_string());
+        self
+    }
+    /// Preferred url of the page, when the same content is reachable from several urls
+    pub fn with_canonical_url(mut self, canonical_url: &str) -> Self {
+        self.head.canonical_url = Some(canonical_url.to_string());
+        self
+    }
+    pub fn with_open_graph(mut self, open_graph: OpenGraph) -> Self {
+        self.head.open_graph = Some(open_graph);
+        self
+    }
+    pub fn with_twitter_card(mut self, twitter_card: TwitterCard) -> Self {
+        self.head.twitter_card = Some(twitter_card);
+        self
+    }
+    pub fn with_robots(mut self, robots: Robots) -> Self {
+        self.head.robots = Some(robots);
+        self
+    }
+    /// Language of the page content, like `en` or `fr-FR`
+    pub fn with_lang(mut self, lang: &str) -> Self {
+        self.head.lang = Some(lang.to_string());
+        self
+    }
+    pub fn with_dir(mut self, dir: TextDirection) -> Self {
+        self.head.dir = Some(dir);
+        self
+    }
+    pub fn with_viewport(mut self, viewport: Viewport) -> Self {
+        self.head.viewport = viewport;
+        self
+    }
+    /// Add a stylesheet loaded after the viewy ones, only on this page
+    pub fn with_stylesheet(mut self, href: &str) -> Self {
+        self.head.stylesheets.push(href.to_string());
+        self
+    }
+    /// Add a javascript module loaded only on this page
+    pub fn with_module_script(mut self, src: &str) -> Self {
+        self.head.module_scripts.push(src.to_string());
+        self
+    }
+    /// Embed the CSRF token sent back by [`Action::Request`](crate::modifiers::Action::Request).
+    ///
+    /// The Rocket binding sets it automatically when the CSRF fairing is attached.
+    pub fn with_csrf_token(mut self, csrf_token: CsrfToken) -> Self {
+        self.csrf_token = Some(csrf_token);
+        self
+    }
+
     pub fn with_content<C>(mut self, content: C) -> Self
     where
         C: Into<Node>,
@@ -75,48 +290,263 @@ impl<'a> Page<'a> {
         self
     }
 
-    pub fn with_layout(mut self, layout: Layout<'a>) -> Self {
-        self.layout = layout.into();
+    /// Render an [`AsyncComponent`] after the page shell, returns the page with the placeholder to
+    /// add to its content.
+    ///
+    /// The component is only streamed by [`Page::render_stream`], [`Page::compile`] keeps its fallback.
+    pub fn defer<C>(mut self, component: C) -> (Self, Node)
+    where
+        C: AsyncComponent,
+    {
+        let id = self.deferred.len();
+        let mut placeholder = Node {
+            html_id: Some(format!("v-deferred-{id}")),
+            ..Default::default()
+        };
+        placeholder
+            .node_style
+            .insert("display".to_string(), "contents".to_string());
+        placeholder.children.push(component.fallback());
+        self.deferred.push(DeferredComponent {
+            id,
+            render: Box::pin(component.render()),
+        });
+        (self, placeholder)
+    }
+
+    /// Whether the page has deferred components, and must be sent with [`Page::render_stream`]
+    pub fn is_streamed(&self) -> bool {
+        !self.deferred.is_empty()
+    }
+
+    /// Add a layout to the page layout chain, the first one is the outermost.
+    pub fn with_layout<L>(mut self, layout: L) -> Self
+    where
+        L: Layout + 'a,
+    {
+        self.layouts.push(Box::new(layout));
+        self
+    }
+
+    /// Post-process the whole tree, layouts included, before rendering. Transforms run in the order they are added.
+    ///
+    /// ```rust,no_run
+    /// # use viewy::prelude::*;
+    /// Page::with_title("Home").with_transform(|root| {
+    ///     root.transform(
+    ///         |node| node.attributes.get("target").is_some_and(|target| target == "_blank"),
+    ///         |link| {
+    ///             link.attributes.insert("rel".to_string(), "noopener".to_string());
+    ///         },
+    ///     )
+    /// });
+    /// ```
+    pub fn with_transform<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(&mut Node) + Send + Sync + 'a,
+    {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Path of the current request, given to the layouts. The Rocket binding sets it automatically.
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_string());
+        self
+    }
+
+    /// Attach a typed value to the page, layouts read it with [`PageContext::extension`]
+    pub fn with_extension<T>(mut self, value: T) -> Self
+    where
+        T: Any + Send + Sync,
+    {
+        self.extensions.insert(value);
+        self
+    }
+
+    /// Inline the styles of the widgets used by the page in the `<head>` and load the full stylesheet asynchronously,
+    /// so that the page is displayed without waiting for `app.css`.
+    pub fn with_critical_css(mut self) -> Self {
+        self.critical_css = true;
         self
     }
 
-    pub fn render_stream(
-        self,
-        render_mode: RenderMode,
-    ) -> Pin<Box<dyn Stream<Item = String> + Send>> {
-        Box::pin(futures::stream::iter(vec![]))
+    /// Enable boosted navigation: links to pages sharing the same layout only fetch the page
+    /// content and swap it in place, keeping the layout (and its state) on screen.
+    ///
+    /// Pages with another layout, or a server that doesn't send the page layout id, are loaded normally.
+    /// The Rocket responder sends it, axum handlers render the page with
+    /// `viewy::bindings::axum::page::PageRequest::respond` to send it.
+    pub fn with_boosted_navigation(mut self) -> Self {
+        self.boosted_navigation = true;
+        self
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Identifies the page layout chain, boosted navigation only swaps the content between pages sharing the same layout id.
+    pub fn layout_id(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        for layout in &self.layouts {
+            layout.id().hash(&mut hasher);
+        }
+        format!("{:x}", hasher.finish())
+    }
+
+    fn apply_layouts(&mut self, content: Node) -> Node {
+        let context = PageContext {
+            title: self.title.clone(),
+            path: self.path.clone(),
+            theme: self.theme.clone(),
+            extensions: std::mem::take(&mut self.extensions),
+        };
+        let content = content_region(content, self.boosted_navigation);
+        let mut root = self
+            .layouts
+            .iter()
+            .rev()
+            .fold(content, |content, layout| layout.render(content, &context));
+        self.apply_transforms(&mut root);
+        root
+    }
+
+    fn apply_transforms(&self, root: &mut Node) {
+        for transform in &self.transforms {
+            transform(root);
+        }
+    }
+
+    /// Stream the page: the shell is sent at once, then each deferred component in the order they resolve.
+    ///
+    /// ```rust,no_run
+    /// # use std::convert::Infallible;
+    /// # use futures::StreamExt;
+    /// # use viewy::prelude::*;
+    /// # #[cfg(feature = "axum")]
+    /// # fn body(page: Page<'static>) -> axum::body::Body {
+    /// # use axum::body::Body;
+    /// // axum
+    /// Body::from_stream(page.render_stream(RenderMode::Complete).map(Ok::<_, Infallible>))
+    /// # }
+    /// ```
+    pub fn render_stream(mut self, render_mode: RenderMode) -> Pin<Box<dyn HtmlStream>> {
+        let deferred = std::mem::take(&mut self.deferred);
+        if deferred.is_empty() || matches!(render_mode, RenderMode::LayoutOnly) {
+            return Box::pin(futures::stream::once(futures::future::ready(
+                self.compile(render_mode),
+            )));
+        }
+
+        let mut assets = StreamedAssets::default();
+        let mut shell = self.render(&render_mode, Some(&mut assets));
+        let closing_tags = match render_mode {
+            RenderMode::Complete => shell
+                .rfind("</body>")
+                .map(|index| shell.split_off(index))
+                .unwrap_or_default(),
+            _ => String::new(),
+        };
+        let resolved = deferred
+            .into_iter()
+            .map(|deferred| async move { (deferred.id, deferred.render.await) })
+            .collect::<FuturesUnordered<_>>()
+            .map(move |(id, content)| resolved_chunk(id, content, &render_mode, &mut assets));
+
+        Box::pin(
+            futures::stream::once(futures::future::ready(shell))
+                .chain(resolved)
+                .chain(futures::stream::once(futures::future::ready(closing_tags))),
+        )
+    }
+
+    fn config(&self) -> &Config {
+        self.config.as_ref().unwrap_or(&CONFIG)
+    }
+
+    fn stylesheet_url(&self) -> String {
+        match &self.config {
+            Some(config) => config_stylesheet(config)
+                .unwrap_or_else(|| {
+                    eprintln!(
+                        "Viewy config warning: the stylesheet of a page config is compiled while \
+                        rendering the page, register it at startup with `register_config_stylesheet`"
+                    );
+                    register_config_stylesheet(config)
+                })
+                .url(),
+            None => app_stylesheet_url(),
+        }
     }
 
     pub fn compile(self, render_mode: RenderMode) -> String {
-        let theme_variant = self.theme.as_str();
+        self.render(&render_mode, None)
+    }
+
+    /// `streamed` is given for the shell of a streamed page, and filled with its assets
+    fn render(mut self, render_mode: &RenderMode, streamed: Option<&mut StreamedAssets>) -> String {
+        let layout_id = self.layout_id();
         let mut html_buffer = String::new();
         match render_mode {
             RenderMode::Complete => {
-                let content = (self.layout)(self.content);
+                let page_content = std::mem::take(&mut self.content);
+                let content = self.apply_layouts(page_content);
                 let mut icon_ids = vec![];
                 collect_used_icon_ids(&content, &mut icon_ids);
                 let sprite = sprite_from_icon_ids(icon_ids.iter().map(|id| id.as_str()));
+                let script_ids = collect_used_script_ids(&content);
+                let widgets = collect_used_widgets(&content);
+                let critical_css = self
+                    .critical_css
+                    .then(|| get_critical_stylesheet_from(self.config(), widgets.iter().copied()));
+                let stylesheet_url = self.stylesheet_url();
 
                 content.render(&mut html_buffer);
-                get_full_html_page(
-                    &self.config,
+                let html = get_full_html_page(
+                    self.config.as_ref().unwrap_or(&CONFIG),
                     self.title,
                     sprite,
                     html_buffer,
-                    theme_variant.to_string(),
+                    &self.theme,
+                    HtmlHead {
+                        page_head: &self.head,
+                        csrf_token: self.csrf_token.as_ref(),
+                        boosted_layout_id: self.boosted_navigation.then_some(&layout_id),
+                        streamed: streamed.is_some(),
+                        script_ids: &script_ids,
+                        critical_css: critical_css.as_deref(),
+                        stylesheet_url: &stylesheet_url,
+                    },
                     false,
-                )
+                );
+                if let Some(assets) = streamed {
+                    assets.widgets = widgets;
+                    assets.script_ids = script_ids;
+                    assets.critical_css = self.critical_css.then(|| self.config.take());
+                }
+                html
             }
             RenderMode::ContentOnly => {
+                let mut content = std::mem::take(&mut self.content);
+                self.apply_transforms(&mut content);
                 let mut icon_ids = vec![];
-                collect_used_icon_ids(&self.content, &mut icon_ids);
+                collect_used_icon_ids(&content, &mut icon_ids);
                 let sprite = sprite_from_icon_ids(icon_ids.iter().map(|id| id.as_str()));
 
-                self.content.render(&mut html_buffer);
+                content.render(&mut html_buffer);
                 format!("{sprite}{html_buffer}")
             }
             RenderMode::LayoutOnly => {
-                let content = (self.layout)(Node {
+                let content = self.apply_layouts(Node {
                     identifier: Uuid::NAMESPACE_OID,
                     node_type: NodeType::Comment("VIEWY_CONTENT"),
                     ..Node::default()
@@ -124,14 +554,28 @@ impl<'a> Page<'a> {
                 let mut icon_ids = vec![];
                 collect_used_icon_ids(&content, &mut icon_ids);
                 let sprite = sprite_from_icon_ids(icon_ids.iter().map(|id| id.as_str()));
+                let script_ids = collect_used_script_ids(&content);
+                let critical_css = self.critical_css.then(|| {
+                    get_critical_stylesheet_from(self.config(), collect_used_widgets(&content))
+                });
+                let stylesheet_url = self.stylesheet_url();
 
                 content.render(&mut html_buffer);
                 get_full_html_page(
-                    &self.config,
+                    self.config.as_ref().unwrap_or(&CONFIG),
                     self.title,
                     sprite,
                     html_buffer,
-                    theme_variant.to_string(),
+                    &self.theme,
+                    HtmlHead {
+                        page_head: &self.head,
+                        csrf_token: self.csrf_token.as_ref(),
+                        boosted_layout_id: self.boosted_navigation.then_some(&layout_id),
+                        streamed: streamed.is_some(),
+                        script_ids: &script_ids,
+                        critical_css: critical_css.as_deref(),
+                        stylesheet_url: &stylesheet_url,
+                    },
                     false,
                 )
             }
@@ -1,15 +1,18 @@
 use crate::CONFIG;
-use crate::core::config::HexColor;
-use crate::core::theme::negative_contrast;
+use crate::core::config::{Config, HexColor};
+use crate::core::theme::tones::{KeyColor, TONES, nearest_tone, tone};
+use crate::core::theme::{high_contrast_color, negative_contrast};
 use crate::prelude::Theme;
 use palette::color_difference::Wcag21RelativeContrast;
 use palette::{
     Darken, Desaturate, Hsluv, IntoColor, Lighten, Saturate, Srgb, Srgba, WithAlpha, WithHue,
 };
-use strum::EnumIter;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use strum::{EnumIter, IntoEnumIterator};
 
 /// Describe UI colors
-#[derive(EnumIter, Copy, Clone, Debug)]
+#[derive(EnumIter, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Color {
     Accent,
     OnAccent,
@@ -32,8 +35,25 @@ pub enum Color {
     DestructiveSurfaceBright,
     Warning,
     WarningSurface,
+    /// Tone of the tonal palette of a key color, rounded to the closest of [`TONES`]
+    #[strum(disabled)]
+    Tone(KeyColor, u8),
 }
 
+/// Names of the tone variables, like `--accent-40`
+static TONE_VARIABLES: LazyLock<HashMap<(KeyColor, u8), String>> = LazyLock::new(|| {
+    KeyColor::iter()
+        .flat_map(|key_color| {
+            TONES.into_iter().map(move |value| {
+                (
+                    (key_color, value),
+                    format!("--{}-{value}", key_color.as_str()),
+                )
+            })
+        })
+        .collect()
+});
+
 impl Color {
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -58,37 +78,46 @@ impl Color {
             Color::DestructiveSurfaceBright => "--destructive-surface-bright",
             Color::Warning => "--warning",
             Color::WarningSurface => "--warning-surface",
+            Color::Tone(key_color, value) => {
+                TONE_VARIABLES[&(*key_color, nearest_tone(*value))].as_str()
+            }
         }
     }
 
     pub fn get_hex_color(&self, theme_variant: &Theme) -> HexColor {
+        self.get_hex_color_from(&CONFIG, theme_variant)
+    }
+
+    /// Color derived from the palette of `config` rather than from `Viewy.toml`.
+    ///
+    /// Like [`Theme::Auto`], [`Theme::HighContrast`] resolves to the light variant: the light high
+    /// contrast palette.
+    pub fn get_hex_color_from(&self, config: &Config, theme_variant: &Theme) -> HexColor {
+        if let Theme::HighContrast = theme_variant {
+            return high_contrast_color(config, *self, &Theme::Light);
+        }
         match self {
             Color::Accent => match theme_variant {
-                Theme::Dark => CONFIG.colors.accent.dark,
-                Theme::Auto | Theme::Light => CONFIG.colors.accent.light,
+                Theme::Dark => config.colors.accent.dark,
+                _ => config.colors.accent.light,
             },
             Color::OnAccent => match theme_variant {
-                Theme::Dark => {
-                    println!("OnAccent dark");
-                    negative_contrast(CONFIG.colors.accent.dark)
-                }
-                Theme::Auto | Theme::Light => {
-                    println!("OnAccent Light");
-                    negative_contrast(CONFIG.colors.accent.light)
-                }
+                Theme::Dark => negative_contrast(config.colors.accent.dark),
+                _ => negative_contrast(config.colors.accent.light),
             },
             Color::Background => match theme_variant {
-                Theme::Dark => CONFIG.colors.background.dark,
-                Theme::Auto | Theme::Light => CONFIG.colors.background.light,
+                Theme::Dark => config.colors.background.dark,
+                _ => config.colors.background.light,
             },
             Color::OnBackground => match theme_variant {
-                Theme::Dark => negative_contrast(CONFIG.colors.background.dark),
-                Theme::Auto | Theme::Light => negative_contrast(CONFIG.colors.background.light),
+                Theme::Dark => negative_contrast(config.colors.background.dark),
+                _ => negative_contrast(config.colors.background.light),
             },
             Color::SurfaceDim => match theme_variant {
                 Theme::Dark => {
-                    let mut surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::Surface, theme_variant).into();
+                    let mut surface: Srgba<u8> = Color::Surface
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = surface.without_alpha().into_linear::<f32>().into_color();
 
                     let lightened_color = color.darken(0.3);
@@ -96,9 +125,10 @@ impl Color {
                         Srgb::from_linear(lightened_color.into_color()).with_alpha(surface.alpha);
                     HexColor::from(surface)
                 }
-                Theme::Auto | Theme::Light => {
-                    let mut surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::Surface, theme_variant).into();
+                _ => {
+                    let mut surface: Srgba<u8> = Color::Surface
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = surface.without_alpha().into_linear::<f32>().into_color();
 
                     let lightened_color = color.darken(0.06);
@@ -109,8 +139,8 @@ impl Color {
             },
             Color::Surface => match theme_variant {
                 Theme::Dark => {
-                    let base: Srgba<u8> = CONFIG.colors.surface.dark.into();
-                    let accent: Srgba<u8> = CONFIG.colors.accent.dark.into();
+                    let base: Srgba<u8> = config.colors.surface.dark.into();
+                    let accent: Srgba<u8> = config.colors.accent.dark.into();
 
                     let base_color: Hsluv = base.without_alpha().into_linear::<f32>().into_color();
                     let accent_color: Hsluv =
@@ -122,9 +152,9 @@ impl Color {
                         Srgb::from_linear(accentuated_surface.into_color()).with_alpha(255),
                     )
                 }
-                Theme::Auto | Theme::Light => {
-                    let base: Srgba<u8> = CONFIG.colors.surface.light.into();
-                    let accent: Srgba<u8> = CONFIG.colors.accent.light.into();
+                _ => {
+                    let base: Srgba<u8> = config.colors.surface.light.into();
+                    let accent: Srgba<u8> = config.colors.accent.light.into();
 
                     let base_color: Hsluv = base.without_alpha().into_linear::<f32>().into_color();
                     let accent_color: Hsluv =
@@ -139,8 +169,9 @@ impl Color {
             },
             Color::SurfaceBright => match theme_variant {
                 Theme::Dark => {
-                    let mut surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::Surface, theme_variant).into();
+                    let mut surface: Srgba<u8> = Color::Surface
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = surface.without_alpha().into_linear::<f32>().into_color();
 
                     let lightened_color = color.lighten(0.06);
@@ -148,9 +179,10 @@ impl Color {
                         Srgb::from_linear(lightened_color.into_color()).with_alpha(surface.alpha);
                     HexColor::from(surface)
                 }
-                Theme::Auto | Theme::Light => {
-                    let mut surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::Surface, theme_variant).into();
+                _ => {
+                    let mut surface: Srgba<u8> = Color::Surface
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = surface.without_alpha().into_linear::<f32>().into_color();
 
                     let lightened_color = color.lighten(0.06);
@@ -161,9 +193,9 @@ impl Color {
             },
             Color::OnSurface => match theme_variant {
                 Theme::Dark => {
-                    let base: Srgba<u8> = negative_contrast(CONFIG.colors.surface.dark).into();
+                    let base: Srgba<u8> = negative_contrast(config.colors.surface.dark).into();
                     let base_color: Hsluv = base.without_alpha().into_linear::<f32>().into_color();
-                    let accent: Srgba<u8> = CONFIG.colors.accent.dark.into();
+                    let accent: Srgba<u8> = config.colors.accent.dark.into();
                     let accent_color: Hsluv =
                         accent.without_alpha().into_linear::<f32>().into_color();
 
@@ -171,16 +203,16 @@ impl Color {
                         .with_hue(accent_color.hue)
                         .saturate(0.8)
                         .darken(0.02);
-                    if !is_contrast_sufficient(CONFIG.colors.surface.dark, on_surface, 4.5) {
+                    if !is_contrast_sufficient(config.colors.surface.dark, on_surface, 4.5) {
                         on_surface = adjust_contrast(on_surface, true);
                     }
 
                     HexColor::from(Srgb::from_linear(on_surface.into_color()).with_alpha(255))
                 }
-                Theme::Auto | Theme::Light => {
-                    let base: Srgba<u8> = negative_contrast(CONFIG.colors.surface.light).into();
+                _ => {
+                    let base: Srgba<u8> = negative_contrast(config.colors.surface.light).into();
                     let base_color: Hsluv = base.without_alpha().into_linear::<f32>().into_color();
-                    let accent: Srgba<u8> = CONFIG.colors.accent.light.into();
+                    let accent: Srgba<u8> = config.colors.accent.light.into();
                     let accent_color: Hsluv =
                         accent.without_alpha().into_linear::<f32>().into_color();
 
@@ -188,7 +220,7 @@ impl Color {
                         .with_hue(accent_color.hue)
                         .saturate(0.8)
                         .lighten(0.02);
-                    if !is_contrast_sufficient(CONFIG.colors.surface.light, on_surface, 4.5) {
+                    if !is_contrast_sufficient(config.colors.surface.light, on_surface, 4.5) {
                         on_surface = adjust_contrast(on_surface, false);
                     }
 
@@ -197,8 +229,9 @@ impl Color {
             },
             Color::AccentuatedSurfaceDim => match theme_variant {
                 Theme::Dark => {
-                    let mut accentuated_surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::AccentuatedSurface, theme_variant).into();
+                    let mut accentuated_surface: Srgba<u8> = Color::AccentuatedSurface
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = accentuated_surface
                         .without_alpha()
                         .into_linear::<f32>()
@@ -209,9 +242,10 @@ impl Color {
                         .with_alpha(accentuated_surface.alpha);
                     HexColor::from(accentuated_surface)
                 }
-                Theme::Auto | Theme::Light => {
-                    let mut accentuated_surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::AccentuatedSurface, theme_variant).into();
+                _ => {
+                    let mut accentuated_surface: Srgba<u8> = Color::AccentuatedSurface
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = accentuated_surface
                         .without_alpha()
                         .into_linear::<f32>()
@@ -225,8 +259,8 @@ impl Color {
             },
             Color::AccentuatedSurface => match theme_variant {
                 Theme::Dark => {
-                    let base: Srgba<u8> = CONFIG.colors.surface.dark.into();
-                    let accent: Srgba<u8> = CONFIG.colors.accent.dark.into();
+                    let base: Srgba<u8> = config.colors.surface.dark.into();
+                    let accent: Srgba<u8> = config.colors.accent.dark.into();
 
                     let base_color: Hsluv = base.without_alpha().into_linear::<f32>().into_color();
                     let accent_color: Hsluv =
@@ -238,9 +272,9 @@ impl Color {
                         Srgb::from_linear(accentuated_surface.into_color()).with_alpha(255),
                     )
                 }
-                Theme::Auto | Theme::Light => {
-                    let base: Srgba<u8> = CONFIG.colors.surface.light.into();
-                    let accent: Srgba<u8> = CONFIG.colors.accent.light.into();
+                _ => {
+                    let base: Srgba<u8> = config.colors.surface.light.into();
+                    let accent: Srgba<u8> = config.colors.accent.light.into();
 
                     let base_color: Hsluv = base.without_alpha().into_linear::<f32>().into_color();
                     let accent_color: Hsluv =
@@ -255,8 +289,9 @@ impl Color {
             },
             Color::AccentuatedSurfaceBright => match theme_variant {
                 Theme::Dark => {
-                    let mut accentuated_surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::AccentuatedSurface, theme_variant).into();
+                    let mut accentuated_surface: Srgba<u8> = Color::AccentuatedSurface
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = accentuated_surface
                         .without_alpha()
                         .into_linear::<f32>()
@@ -267,9 +302,10 @@ impl Color {
                         .with_alpha(accentuated_surface.alpha);
                     HexColor::from(accentuated_surface)
                 }
-                Theme::Auto | Theme::Light => {
-                    let mut accentuated_surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::AccentuatedSurface, theme_variant).into();
+                _ => {
+                    let mut accentuated_surface: Srgba<u8> = Color::AccentuatedSurface
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = accentuated_surface
                         .without_alpha()
                         .into_linear::<f32>()
@@ -282,18 +318,21 @@ impl Color {
                 }
             },
             Color::Border => {
-                let background: Srgba<u8> =
-                    Color::get_hex_color(&Color::OnBackground, theme_variant).into();
+                let background: Srgba<u8> = Color::OnBackground
+                    .get_hex_color_from(config, theme_variant)
+                    .into();
                 let background_color: Hsluv =
                     background.without_alpha().into_linear::<f32>().into_color();
-                let accent: Srgba<u8> = Color::get_hex_color(&Color::Accent, theme_variant).into();
+                let accent: Srgba<u8> = Color::Accent
+                    .get_hex_color_from(config, theme_variant)
+                    .into();
                 let accent_color: Hsluv = accent.without_alpha().into_linear::<f32>().into_color();
                 let border = match theme_variant {
                     Theme::Dark => background_color
                         .with_hue(accent_color.hue)
                         .darken(0.7)
                         .desaturate(0.9),
-                    Theme::Auto | Theme::Light => background_color
+                    _ => background_color
                         .with_hue(accent_color.hue)
                         .lighten(0.85)
                         .desaturate(0.9),
@@ -302,13 +341,14 @@ impl Color {
                 HexColor::from(Srgb::from_linear(border.into_color()).with_alpha(255))
             }
             Color::Success => match theme_variant {
-                Theme::Dark => CONFIG.colors.success.dark,
-                Theme::Auto | Theme::Light => CONFIG.colors.success.light,
+                Theme::Dark => config.colors.success.dark,
+                _ => config.colors.success.light,
             },
             Color::SuccessSurface => match theme_variant {
                 Theme::Dark => {
-                    let mut success_surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::Success, theme_variant).into();
+                    let mut success_surface: Srgba<u8> = Color::Success
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = success_surface
                         .without_alpha()
                         .into_linear::<f32>()
@@ -319,9 +359,10 @@ impl Color {
                         .with_alpha(success_surface.alpha);
                     HexColor::from(success_surface)
                 }
-                Theme::Auto | Theme::Light => {
-                    let mut success_surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::Success, theme_variant).into();
+                _ => {
+                    let mut success_surface: Srgba<u8> = Color::Success
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = success_surface
                         .without_alpha()
                         .into_linear::<f32>()
@@ -334,17 +375,18 @@ impl Color {
                 }
             },
             Color::Destructive => match theme_variant {
-                Theme::Dark => CONFIG.colors.destructive.dark,
-                Theme::Auto | Theme::Light => CONFIG.colors.destructive.light,
+                Theme::Dark => config.colors.destructive.dark,
+                _ => config.colors.destructive.light,
             },
             Color::OnDestructive => match theme_variant {
-                Theme::Dark => negative_contrast(CONFIG.colors.destructive.dark),
-                Theme::Auto | Theme::Light => negative_contrast(CONFIG.colors.destructive.light),
+                Theme::Dark => negative_contrast(config.colors.destructive.dark),
+                _ => negative_contrast(config.colors.destructive.light),
             },
             Color::DestructiveSurfaceDim => match theme_variant {
                 Theme::Dark => {
-                    let mut destructive_surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::DestructiveSurface, theme_variant).into();
+                    let mut destructive_surface: Srgba<u8> = Color::DestructiveSurface
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = destructive_surface
                         .without_alpha()
                         .into_linear::<f32>()
@@ -355,9 +397,10 @@ impl Color {
                         .with_alpha(destructive_surface.alpha);
                     HexColor::from(destructive_surface)
                 }
-                Theme::Auto | Theme::Light => {
-                    let mut destructive_surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::DestructiveSurface, theme_variant).into();
+                _ => {
+                    let mut destructive_surface: Srgba<u8> = Color::DestructiveSurface
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = destructive_surface
                         .without_alpha()
                         .into_linear::<f32>()
@@ -371,8 +414,8 @@ impl Color {
             },
             Color::DestructiveSurface => match theme_variant {
                 Theme::Dark => {
-                    let base: Srgba<u8> = CONFIG.colors.surface.dark.into();
-                    let destructive: Srgba<u8> = CONFIG.colors.destructive.dark.into();
+                    let base: Srgba<u8> = config.colors.surface.dark.into();
+                    let destructive: Srgba<u8> = config.colors.destructive.dark.into();
 
                     let base_color: Hsluv = base.without_alpha().into_linear::<f32>().into_color();
                     let destructive_color: Hsluv = destructive
@@ -387,9 +430,9 @@ impl Color {
                         Srgb::from_linear(destructive_surface.into_color()).with_alpha(255),
                     )
                 }
-                Theme::Auto | Theme::Light => {
-                    let base: Srgba<u8> = CONFIG.colors.surface.light.into();
-                    let destructive: Srgba<u8> = CONFIG.colors.destructive.light.into();
+                _ => {
+                    let base: Srgba<u8> = config.colors.surface.light.into();
+                    let destructive: Srgba<u8> = config.colors.destructive.light.into();
 
                     let base_color: Hsluv = base.without_alpha().into_linear::<f32>().into_color();
                     let destructive_color: Hsluv = destructive
@@ -407,8 +450,9 @@ impl Color {
             },
             Color::DestructiveSurfaceBright => match theme_variant {
                 Theme::Dark => {
-                    let mut destructive_surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::DestructiveSurface, theme_variant).into();
+                    let mut destructive_surface: Srgba<u8> = Color::DestructiveSurface
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = destructive_surface
                         .without_alpha()
                         .into_linear::<f32>()
@@ -419,9 +463,10 @@ impl Color {
                         .with_alpha(destructive_surface.alpha);
                     HexColor::from(destructive_surface)
                 }
-                Theme::Auto | Theme::Light => {
-                    let mut destructive_surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::DestructiveSurface, theme_variant).into();
+                _ => {
+                    let mut destructive_surface: Srgba<u8> = Color::DestructiveSurface
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = destructive_surface
                         .without_alpha()
                         .into_linear::<f32>()
@@ -433,14 +478,18 @@ impl Color {
                     HexColor::from(destructive_surface)
                 }
             },
+            Color::Tone(key_color, value) => {
+                tone(key_color.get_hex_color_from(config), nearest_tone(*value))
+            }
             Color::Warning => match theme_variant {
-                Theme::Dark => CONFIG.colors.warning.dark,
-                Theme::Auto | Theme::Light => CONFIG.colors.warning.light,
+                Theme::Dark => config.colors.warning.dark,
+                _ => config.colors.warning.light,
             },
             Color::WarningSurface => match theme_variant {
                 Theme::Dark => {
-                    let mut warning_surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::Warning, theme_variant).into();
+                    let mut warning_surface: Srgba<u8> = Color::Warning
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = warning_surface
                         .without_alpha()
                         .into_linear::<f32>()
@@ -451,9 +500,10 @@ impl Color {
                         .with_alpha(warning_surface.alpha);
                     HexColor::from(warning_surface)
                 }
-                Theme::Auto | Theme::Light => {
-                    let mut warning_surface: Srgba<u8> =
-                        Color::get_hex_color(&Color::Warning, theme_variant).into();
+                _ => {
+                    let mut warning_surface: Srgba<u8> = Color::Warning
+                        .get_hex_color_from(config, theme_variant)
+                        .into();
                     let color: Hsluv = warning_surface
                         .without_alpha()
                         .into_linear::<f32>()
@@ -0,0 +1,253 @@
+use crate::core::config::{Config, HexColor};
+use crate::core::theme::{Color, Theme};
+use palette::color_difference::Wcag21RelativeContrast;
+use palette::{Clamp, FromColor, Oklch, Srgb, Srgba};
+use std::fmt;
+
+/// WCAG AA minimum contrast ratio of normal text
+pub const AA_TEXT_CONTRAST: f32 = 4.5;
+/// WCAG AA minimum contrast ratio of borders and other non-text UI components
+pub const AA_NON_TEXT_CONTRAST: f32 = 3.0;
+/// WCAG AAA minimum contrast ratio of normal text, reached by the high contrast palette
+pub const AAA_TEXT_CONTRAST: f32 = 7.0;
+
+/// Foreground color used on a background color by the widgets
+#[derive(Debug, Clone, Copy)]
+pub struct ContrastPairing {
+    pub foreground: Color,
+    pub background: Color,
+    pub minimum_ratio: f32,
+    /// Used by disabled widgets only, which the high contrast styles recolor
+    pub disabled: bool,
+}
+
+const fn text(foreground: Color, background: Color) -> ContrastPairing {
+    ContrastPairing {
+        foreground,
+        background,
+        minimum_ratio: AA_TEXT_CONTRAST,
+        disabled: false,
+    }
+}
+
+const fn disabled(foreground: Color, background: Color) -> ContrastPairing {
+    ContrastPairing {
+        disabled: true,
+        ..text(foreground, background)
+    }
+}
+
+const fn outline(foreground: Color, background: Color) -> ContrastPairing {
+    ContrastPairing {
+        foreground,
+        background,
+        minimum_ratio: AA_NON_TEXT_CONTRAST,
+        disabled: false,
+    }
+}
+
+/// Text and outline colors on the backgrounds they are used with in the widget styles,
+/// hover and active backgrounds included
+pub const WIDGET_PAIRINGS: [ContrastPairing; 21] = [
+    text(Color::OnBackground, Color::Background),
+    text(Color::OnBackground, Color::AccentuatedSurfaceBright),
+    text(Color::OnSurface, Color::Background),
+    text(Color::OnSurface, Color::Surface),
+    text(Color::OnSurface, Color::SurfaceDim),
+    text(Color::OnSurface, Color::SurfaceBright),
+    text(Color::Accent, Color::Background),
+    text(Color::Accent, Color::Surface),
+    text(Color::Accent, Color::AccentuatedSurfaceBright),
+    text(Color::Accent, Color::AccentuatedSurface),
+    text(Color::Accent, Color::AccentuatedSurfaceDim),
+    text(Color::OnAccent, Color::Accent),
+    text(Color::Destructive, Color::Background),
+    text(Color::Destructive, Color::DestructiveSurfaceBright),
+    text(Color::Destructive, Color::DestructiveSurface),
+    text(Color::Destructive, Color::DestructiveSurfaceDim),
+    text(Color::OnDestructive, Color::Destructive),
+    disabled(Color::OnDestructive, Color::DestructiveSurface),
+    disabled(Color::OnDestructive, Color::SurfaceDim),
+    outline(Color::Border, Color::Background),
+    outline(Color::Border, Color::Surface),
+];
+
+/// Pairing below its minimum contrast ratio in a theme
+#[derive(Debug, Clone)]
+pub struct ContrastViolation {
+    pub pairing: ContrastPairing,
+    pub theme: Theme,
+    pub foreground: HexColor,
+    pub background: HexColor,
+    pub ratio: f32,
+    /// Foreground with the same hue, lightened or darkened to reach the minimum ratio
+    pub suggestion: HexColor,
+}
+
+impl fmt::Display for ContrastViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}) on {} ({}) in {} theme: {:.2}:1, expected {}:1, try {}",
+            self.pairing.foreground.as_str(),
+            self.foreground.to_string(),
+            self.pairing.background.as_str(),
+            self.background.to_string(),
+            self.theme.as_str(),
+            self.ratio,
+            self.pairing.minimum_ratio,
+            self.suggestion.to_string()
+        )
+    }
+}
+
+fn to_srgb(color: HexColor) -> Srgb<f32> {
+    let color: Srgba<u8> = color.into();
+    color.color.into_format()
+}
+
+/// WCAG 2.1 contrast ratio of two colors, from 1 to 21
+pub fn contrast_ratio(foreground: HexColor, background: HexColor) -> f32 {
+    to_srgb(foreground).relative_contrast(to_srgb(background))
+}
+
+/// `foreground` with the smallest lightness change reaching `minimum_ratio` on `background`,
+/// black or white when no lightness reaches it
+pub fn suggest_foreground(
+    foreground: HexColor,
+    background: HexColor,
+    minimum_ratio: f32,
+) -> HexColor {
+    let original = Oklch::from_color(to_srgb(foreground));
+    let with_lightness = |lightness: f32| {
+        let color = Srgb::from_color(Oklch::new(lightness, original.chroma, original.hue))
+            .clamp()
+            .into_format::<u8>();
+        HexColor([color.red, color.green, color.blue, 255])
+    };
+    let black = HexColor([0, 0, 0, 255]);
+    let white = HexColor([255, 255, 255, 255]);
+    let on_black = contrast_ratio(black, background);
+    let (limit, extreme) = if on_black >= contrast_ratio(white, background) {
+        (0.0, black)
+    } else {
+        (1.0, white)
+    };
+    if contrast_ratio(extreme, background) < minimum_ratio {
+        return extreme;
+    }
+
+    // Lightness between the original, too close to the background, and the limit
+    let (mut close, mut far) = (original.l, limit);
+    for _ in 0..20 {
+        let middle = (close + far) / 2.0;
+        if contrast_ratio(with_lightness(middle), background) >= minimum_ratio {
+            far = middle;
+        } else {
+            close = middle;
+        }
+    }
+    let suggestion = with_lightness(far);
+    if contrast_ratio(suggestion, background) >= minimum_ratio {
+        suggestion
+    } else {
+        extreme
+    }
+}
+
+/// Check every widget pairing of the palette of `config`, in the light, dark and high contrast themes
+pub fn audit_contrast(config: &Config) -> Vec<ContrastViolation> {
+    audit_pairings(config, &WIDGET_PAIRINGS)
+}
+
+/// Check `pairings` with the palette of `config`, in the light and dark themes, then with the high
+/// contrast palette derived from them against [`AAA_TEXT_CONTRAST`], disabled pairings excepted
+pub fn audit_pairings(config: &Config, pairings: &[ContrastPairing]) -> Vec<ContrastViolation> {
+    let palette = [Theme::Light, Theme::Dark].into_iter().flat_map(|theme| {
+        pairings.iter().filter_map(move |pairing| {
+            check_pairing(
+                *pairing,
+                theme.clone(),
+                pairing.foreground.get_hex_color_from(config, &theme),
+                pairing.background.get_hex_color_from(config, &theme),
+            )
+        })
+    });
+    // Both variants of the high contrast palette are reported as the high contrast theme
+    let high_contrast_palette = [Theme::Light, Theme::Dark].into_iter().flat_map(|theme| {
+        pairings
+            .iter()
+            .filter(|pairing| !pairing.disabled)
+            .filter_map(move |pairing| {
+                check_pairing(
+                    ContrastPairing {
+                        minimum_ratio: high_contrast_ratio(pairing),
+                        ..*pairing
+                    },
+                    Theme::HighContrast,
+                    high_contrast_color(config, pairing.foreground, &theme),
+                    high_contrast_color(config, pairing.background, &theme),
+                )
+            })
+    });
+    palette.chain(high_contrast_palette).collect()
+}
+
+fn check_pairing(
+    pairing: ContrastPairing,
+    theme: Theme,
+    foreground: HexColor,
+    background: HexColor,
+) -> Option<ContrastViolation> {
+    let ratio = contrast_ratio(foreground, background);
+    (ratio < pairing.minimum_ratio).then(|| ContrastViolation {
+        pairing,
+        theme,
+        foreground,
+        background,
+        ratio,
+        suggestion: suggest_foreground(foreground, background, pairing.minimum_ratio),
+    })
+}
+
+/// Minimum ratio of `pairing` in the high contrast palette: AAA for text, AA text for outlines
+fn high_contrast_ratio(pairing: &ContrastPairing) -> f32 {
+    if pairing.minimum_ratio >= AA_TEXT_CONTRAST {
+        AAA_TEXT_CONTRAST
+    } else {
+        AA_TEXT_CONTRAST
+    }
+}
+
+/// Color of the high contrast palette, derived from the palette of `config` in the light or dark `theme`.
+///
+/// The background is pure white or black, and each foreground of [`WIDGET_PAIRINGS`] is lightened
+/// or darkened to reach [`AAA_TEXT_CONTRAST`] on its backgrounds, or [`AA_TEXT_CONTRAST`] for outlines.
+/// Disabled pairings are left out, the high contrast styles recolor disabled widgets.
+pub fn high_contrast_color(config: &Config, color: Color, theme: &Theme) -> HexColor {
+    let theme = match theme {
+        Theme::Dark => Theme::Dark,
+        _ => Theme::Light,
+    };
+    if color == Color::Background {
+        return match theme {
+            Theme::Dark => HexColor([0, 0, 0, 255]),
+            _ => HexColor([255, 255, 255, 255]),
+        };
+    }
+    WIDGET_PAIRINGS
+        .iter()
+        .filter(|pairing| pairing.foreground == color && !pairing.disabled)
+        .fold(
+            color.get_hex_color_from(config, &theme),
+            |foreground, pairing| {
+                let background = high_contrast_color(config, pairing.background, &theme);
+                let minimum_ratio = high_contrast_ratio(pairing);
+                if contrast_ratio(foreground, background) >= minimum_ratio {
+                    foreground
+                } else {
+                    suggest_foreground(foreground, background, minimum_ratio)
+                }
+            },
+        )
+}
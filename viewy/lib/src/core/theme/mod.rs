@@ -8,20 +8,44 @@ use palette::color_difference::Wcag21RelativeContrast;
 use strum::IntoEnumIterator;
 
 pub use colors::Color;
+pub use contrast::{
+    AA_NON_TEXT_CONTRAST, AA_TEXT_CONTRAST, AAA_TEXT_CONTRAST, ContrastPairing, ContrastViolation,
+    WIDGET_PAIRINGS, audit_contrast, audit_pairings, contrast_ratio, high_contrast_color,
+    suggest_foreground,
+};
+pub use tones::{KeyColor, TONES, tone};
+use tones::generate_tonal_palette;
 
-use crate::core::config::HexColor;
+use crate::core::assets::is_registered_theme;
+use crate::core::config::{Config, ContrastCheck, HexColor, StyleSettings};
 use crate::{sp, CONFIG};
-use crate::widgets::get_all_stylesheet;
+use crate::widgets::{get_all_stylesheet, get_widget_stylesheet};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{LazyLock, RwLock};
 
 mod colors;
+mod contrast;
 mod fonts;
 mod sizing;
+mod tones;
 
-#[derive(Debug, Clone)]
+/// Cookie storing the theme picked with a [`ThemeSwitcher`](crate::widgets::theme_switcher::ThemeSwitcher)
+pub const THEME_COOKIE: &str = "viewy_theme";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Theme {
     Dark,
     Light,
     Auto,
+    /// Light or dark depending on the user preference, with the high contrast palette. Outside of
+    /// the stylesheet, as in [`Color::get_hex_color_from`], the light high contrast palette is used
+    HighContrast,
 }
 
 impl Theme {
@@ -30,6 +54,22 @@ impl Theme {
             Theme::Dark => "dark",
             Theme::Light => "light",
             Theme::Auto => "auto",
+            Theme::HighContrast => "high-contrast",
+        }
+    }
+}
+
+/// Parse the names given by [`Theme::as_str`], like the value of the [`THEME_COOKIE`] cookie
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim() {
+            "dark" => Ok(Theme::Dark),
+            "light" => Ok(Theme::Light),
+            "auto" => Ok(Theme::Auto),
+            "high-contrast" => Ok(Theme::HighContrast),
+            other => Err(format!("unknown theme `{other}`")),
         }
     }
 }
@@ -45,40 +85,69 @@ fn negative_contrast(color: HexColor) -> HexColor {
     }
 }
 
-fn generate_color_palette(theme_variant: Theme) -> String {
-    let palette = Color::iter()
+/// Color variables with their light and dark values
+fn color_variables(get_hex_color: impl Fn(Color, &Theme) -> HexColor) -> String {
+    Color::iter()
         .map(|color| {
             format!(
                 "{}: light-dark({}, {});",
                 color.as_str(),
-                color.get_hex_color(&Theme::Light).to_string(),
-                color.get_hex_color(&Theme::Dark).to_string()
+                get_hex_color(color, &Theme::Light).to_string(),
+                get_hex_color(color, &Theme::Dark).to_string()
             )
         })
         .collect::<Vec<String>>()
-        .join("");
+        .join("")
+}
+
+fn generate_color_palette(config: &Config) -> String {
+    let palette = color_variables(|color, theme| color.get_hex_color_from(config, theme));
+    let high_contrast_palette =
+        color_variables(|color, theme| high_contrast_color(config, color, theme));
+    let tonal_palette = generate_tonal_palette(config);
 
-    let color_scheme_param = match theme_variant {
-        Theme::Dark => "color-scheme: dark;",
-        Theme::Light => "color-scheme: light;",
-        Theme::Auto => "color-scheme: light dark;",
-    };
     format!(
         r#"
     :root {{
-     {color_scheme_param}
+     color-scheme: light dark;
      {palette}
+     {tonal_palette}
+    }}
+
+    .app-theme--light {{
+     color-scheme: light;
+    }}
+
+    .app-theme--dark {{
+     color-scheme: dark;
+    }}
+
+    @media (prefers-contrast: more) {{
+        :root {{
+         {high_contrast_palette}
+        }}
+    }}
+
+    .app-theme--high-contrast {{
+     {high_contrast_palette}
     }}
     "#
     )
 }
 
+thread_local! {
+    /// Spacing factor of the config being compiled, grass functions can't capture it
+    static SPACING_FACTOR: Cell<Option<i32>> = const { Cell::new(None) };
+}
+
 pub fn grass_scale(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<Value> {
     args.max_args(1)?;
     let scale_arg = args.get(0, "scale").expect("$scale argument must be defined").node;
     if let Value::Dimension(num) = scale_arg {
         let real_scale = num.num.0;
-        let real_spacing_factor = CONFIG.shapes.spacing_factor as f64;
+        let real_spacing_factor = SPACING_FACTOR
+            .get()
+            .unwrap_or(CONFIG.shapes.spacing_factor) as f64;
         let rem_val = real_scale.powf(real_spacing_factor.sqrt()).ceil() / 16.0;
         Ok(Value::Dimension(SassNumber {
             num: rem_val.into(),
@@ -108,14 +177,79 @@ pub fn grass_sp(mut args: ArgumentResult, visitor: &mut Visitor) -> SassResult<V
 
 }
 
-pub fn get_stylesheet() -> String {
-    let palette_style = generate_color_palette(Theme::Auto);
-    let widget_style = get_all_stylesheet().join("");
+/// Compile with the shapes of `config`: `scale()` uses its spacing factor and `$border-radius` is defined
+fn compile_scss(config: &Config, scss: String, load_paths: &[PathBuf]) -> String {
     let options = grass::Options::default()
         .style(OutputStyle::Compressed)
+        .load_paths(load_paths)
         .add_custom_fn("sp", Builtin::new(grass_sp))
         .add_custom_fn("scale", Builtin::new(grass_scale));
-    grass::from_string(
+    let scss = format!("$border-radius: {};\n{scss}", config.shapes.border_radius);
+    let previous_spacing_factor = SPACING_FACTOR.replace(Some(config.shapes.spacing_factor));
+    let compiled = grass::from_string(scss, &options);
+    SPACING_FACTOR.set(previous_spacing_factor);
+    compiled.expect("Can't compile SCSS")
+}
+
+/// Stylesheets of a directory in path order, partials excepted
+fn collect_style_files(directory: &Path, files: &mut Vec<PathBuf>) {
+    let mut entries = fs::read_dir(directory)
+        .unwrap_or_else(|err| panic!("Can't read styles directory {}: {err}", directory.display()))
+        .flatten()
+        .map(|entry| entry.path())
+        .collect::<Vec<PathBuf>>();
+    entries.sort();
+    for path in entries {
+        let is_partial = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('_'));
+        let is_stylesheet = path
+            .extension()
+            .is_some_and(|extension| extension == "scss" || extension == "css");
+        if path.is_dir() {
+            collect_style_files(&path, files);
+        } else if is_stylesheet && !is_partial {
+            files.push(path);
+        }
+    }
+}
+
+/// Application styles listed in the config, with the directories to resolve their imports from
+fn load_user_styles(styles: &StyleSettings) -> (String, Vec<PathBuf>) {
+    let mut files = vec![];
+    let mut load_paths = vec![];
+    for source in &styles.sources {
+        if source.is_dir() {
+            collect_style_files(source, &mut files);
+            load_paths.push(source.clone());
+        } else {
+            files.push(source.clone());
+            if let Some(parent) = source.parent() {
+                load_paths.push(parent.to_path_buf());
+            }
+        }
+    }
+    let scss = files
+        .iter()
+        .map(|file| {
+            fs::read_to_string(file)
+                .unwrap_or_else(|err| panic!("Can't read stylesheet {}: {err}", file.display()))
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    (scss, load_paths)
+}
+
+/// Palette, widget styles and application styles of `config` compiled in one pass, the palette
+/// being audited first when `accessibility.contrast-check` is enabled
+pub fn compile_stylesheet(config: &Config) -> String {
+    check_contrast(config);
+    let palette_style = generate_color_palette(config);
+    let widget_style = get_all_stylesheet().join("");
+    let (user_style, load_paths) = load_user_styles(&config.styles);
+    compile_scss(
+        config,
         format!(
             r#"
 
@@ -124,9 +258,119 @@ pub fn get_stylesheet() -> String {
 
 
         {widget_style}
+
+
+        {user_style}
         "#
         ),
-        &options,
+        &load_paths,
     )
-        .expect("Can't compile SCSS")
+}
+
+fn check_contrast(config: &Config) {
+    let check = config.accessibility.contrast_check;
+    if check == ContrastCheck::Off {
+        return;
+    }
+    let violations = audit_contrast(config);
+    if violations.is_empty() {
+        return;
+    }
+    let report: String = violations
+        .iter()
+        .map(|violation| format!("\n  - {violation}"))
+        .collect();
+    match check {
+        ContrastCheck::Error => panic!("Viewy palette fails the contrast check:{report}"),
+        _ => eprintln!("Viewy palette fails the contrast check:{report}"),
+    }
+}
+
+pub fn get_stylesheet() -> String {
+    compile_stylesheet(&CONFIG)
+}
+
+/// Hash of the settings of `config` the stylesheet is compiled from
+pub(crate) fn theme_key(config: &Config) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    format!("{:?}{:?}{:?}", config.colors, config.shapes, config.styles).hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Parts of the critical stylesheets, by theme key and part: the palette, a widget or the application styles.
+///
+/// Only the parts of the application theme and of the registered configs are kept.
+static COMPILED_PARTS: LazyLock<RwLock<HashMap<(u64, String), String>>> =
+    LazyLock::new(Default::default);
+
+fn compiled_part(
+    config: &Config,
+    part: &str,
+    scss: impl FnOnce() -> (String, Vec<PathBuf>),
+) -> String {
+    let key = (theme_key(config), part.to_string());
+    if let Some(compiled) = COMPILED_PARTS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&key)
+    {
+        return compiled.clone();
+    }
+    let (scss, load_paths) = scss();
+    let compiled = compile_scss(config, scss, &load_paths);
+    if is_registered_theme(key.0) {
+        COMPILED_PARTS
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(key, compiled.clone());
+    }
+    compiled
+}
+
+/// Palette, styles of the given widgets only and application styles, to inline the CSS needed by a page.
+///
+/// `widgets` are widget origins, as in [`Node::origin`](crate::node::Node), styles are compiled once.
+pub fn get_critical_stylesheet<'a, I>(widgets: I) -> String
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    get_critical_stylesheet_from(&CONFIG, widgets)
+}
+
+/// [`get_critical_stylesheet`] with the theme of `config` rather than of `Viewy.toml`
+pub fn get_critical_stylesheet_from<'a, I>(config: &Config, widgets: I) -> String
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut stylesheet = compiled_part(config, "palette", || {
+        (generate_color_palette(config), vec![])
+    });
+    stylesheet.push_str(&get_widgets_stylesheet_from(config, widgets));
+    stylesheet.push_str(&compiled_part(config, "user", || {
+        load_user_styles(&config.styles)
+    }));
+    stylesheet
+}
+
+/// Styles of the given widgets only, with the theme of `config`, to complete a critical stylesheet
+pub fn get_widgets_stylesheet_from<'a, I>(config: &Config, widgets: I) -> String
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut stylesheet = String::new();
+    let mut included: Vec<&'static str> = vec![];
+    for widget in widgets {
+        let Some(style) = get_widget_stylesheet(widget) else {
+            continue;
+        };
+        // Widgets sharing a stylesheet
+        if included.contains(&style) {
+            continue;
+        }
+        included.push(style);
+        stylesheet.push_str(&compiled_part(config, widget, || {
+            (style.to_string(), vec![])
+        }));
+    }
+    stylesheet
 }
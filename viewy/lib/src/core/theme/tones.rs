@@ -0,0 +1,124 @@
+use crate::core::config::{Config, HexColor};
+use palette::{Clamp, FromColor, IsWithinBounds, Oklch, Srgb, Srgba};
+use strum::EnumIter;
+
+/// Tones exposed as CSS variables and [`Color::Tone`](super::Color::Tone), from black (0) to white (100)
+pub const TONES: [u8; 13] = [0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 95, 99, 100];
+
+/// Colors a tonal palette is generated from
+#[derive(EnumIter, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum KeyColor {
+    Accent,
+    Secondary,
+    Tertiary,
+    Neutral,
+    Destructive,
+    Success,
+    Warning,
+}
+
+impl KeyColor {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyColor::Accent => "accent",
+            KeyColor::Secondary => "secondary",
+            KeyColor::Tertiary => "tertiary",
+            KeyColor::Neutral => "neutral",
+            KeyColor::Destructive => "destructive",
+            KeyColor::Success => "success",
+            KeyColor::Warning => "warning",
+        }
+    }
+
+    /// Key color set in `config`, secondary, tertiary and neutral being derived from the accent when not set
+    pub fn get_hex_color_from(&self, config: &Config) -> HexColor {
+        let colors = &config.colors;
+        let accent = || to_oklch(colors.accent.light);
+        match self {
+            KeyColor::Accent => colors.accent.light,
+            KeyColor::Secondary => colors.secondary.unwrap_or_else(|| {
+                let accent = accent();
+                from_oklch(Oklch::new(accent.l, accent.chroma / 3.0, accent.hue))
+            }),
+            KeyColor::Tertiary => colors.tertiary.unwrap_or_else(|| {
+                let accent = accent();
+                from_oklch(Oklch::new(accent.l, accent.chroma, accent.hue + 60.0))
+            }),
+            KeyColor::Neutral => colors.neutral.unwrap_or_else(|| {
+                let accent = accent();
+                from_oklch(Oklch::new(accent.l, accent.chroma.min(0.02), accent.hue))
+            }),
+            KeyColor::Destructive => colors.destructive.light,
+            KeyColor::Success => colors.success.light,
+            KeyColor::Warning => colors.warning.light,
+        }
+    }
+}
+
+fn to_oklch(color: HexColor) -> Oklch {
+    let color: Srgba<u8> = color.into();
+    Oklch::from_color(color.color.into_format::<f32>())
+}
+
+fn from_oklch(color: Oklch) -> HexColor {
+    let color = Srgb::from_color(color).clamp().into_format::<u8>();
+    HexColor([color.red, color.green, color.blue, 255])
+}
+
+/// Tone of `key`, in `0..=100`: the Oklch lightness of the tone with the hue of the key color.
+///
+/// The chroma of the key color is kept when the tone can be displayed with it, and reduced
+/// otherwise, so that light and dark tones stay in the sRGB gamut.
+pub fn tone(key: HexColor, tone: u8) -> HexColor {
+    // Colors with a chroma are never black or white, whatever their lightness
+    match tone {
+        0 => return HexColor([0, 0, 0, 255]),
+        100.. => return HexColor([255, 255, 255, 255]),
+        _ => {}
+    }
+    let key = to_oklch(key);
+    let lightness = f32::from(tone) / 100.0;
+    let in_gamut =
+        |chroma: f32| Srgb::from_color(Oklch::new(lightness, chroma, key.hue)).is_within_bounds();
+
+    let mut chroma = key.chroma;
+    if !in_gamut(chroma) {
+        let (mut low, mut high) = (0.0, chroma);
+        for _ in 0..16 {
+            let middle = (low + high) / 2.0;
+            if in_gamut(middle) {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        chroma = low;
+    }
+    from_oklch(Oklch::new(lightness, chroma, key.hue))
+}
+
+/// Tone of [`TONES`] closest to `tone`
+pub fn nearest_tone(tone: u8) -> u8 {
+    TONES
+        .into_iter()
+        .min_by_key(|candidate| candidate.abs_diff(tone))
+        .unwrap_or(tone)
+}
+
+/// CSS variables of the tonal palettes of `config`, like `--accent-40`
+pub(super) fn generate_tonal_palette(config: &Config) -> String {
+    use strum::IntoEnumIterator;
+
+    KeyColor::iter()
+        .flat_map(|key_color| {
+            let key = key_color.get_hex_color_from(config);
+            TONES.into_iter().map(move |value| {
+                format!(
+                    "--{}-{value}: {};",
+                    key_color.as_str(),
+                    tone(key, value).to_string()
+                )
+            })
+        })
+        .collect()
+}
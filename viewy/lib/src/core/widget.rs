@@ -1,8 +1,84 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::ops::{Deref, DerefMut};
+use std::sync::LazyLock;
 
 use super::node::Node;
 
+/// Style of a widget, registered by `#[derive(Widget)]` and compiled in the application stylesheet
+pub struct WidgetStyle {
+    /// [`Widget::ORIGIN`] of the widget
+    pub widget: &'static str,
+    pub style: &'static str,
+}
+
+inventory::collect!(WidgetStyle);
+
+/// Script of a widget, registered by `#[derive(Widget)]` when set with `#[widget(script = "...")]`
+pub struct WidgetScript {
+    /// [`Widget::ORIGIN`] of the widget
+    pub widget: &'static str,
+    pub script: &'static str,
+}
+
+inventory::collect!(WidgetScript);
+
+/// Origin of a widget linked in the binary, to restore [`Node::origin`] without allocating
+pub(crate) fn registered_widget(origin: &str) -> Option<&'static str> {
+    inventory::iter::<WidgetStyle>
+        .into_iter()
+        .find(|widget_style| widget_style.widget == origin)
+        .map(|widget_style| widget_style.widget)
+}
+
+/// Attribute marking the root node of the widgets having a script, with the script id
+pub const SCRIPT_ATTRIBUTE: &str = "data-v-script";
+
 pub trait Widget: Deref<Target = Node> + DerefMut<Target = Node> + Into<Node> {
+    /// Identifier of the widget set in the [`Node::origin`] of its root node: its module path
+    /// and name, like `viewy::widgets::button::Button`
+    const ORIGIN: &'static str;
     const STYLE: &'static str;
+    /// Javascript module giving the widget its behaviour, set with `#[widget(script = "./script.js")]`.
+    ///
+    /// The module exports an `init(root)` function, called with each rendered or injected root
+    /// containing the widget. It is only loaded by the pages using the widget.
+    const SCRIPT: Option<&'static str> = None;
+
     fn widget_name() -> &'static str;
+
+    /// Id of the widget script, served at [`widget_script_url`](crate::prelude::widget_script_url)
+    fn script_id() -> Option<String>
+    where
+        Self: Sized,
+    {
+        SCRIPT_REGISTRY.ids.get(Self::ORIGIN).cloned()
+    }
+}
+
+#[derive(Default)]
+struct ScriptRegistry {
+    /// Script ids by widget origin
+    ids: HashMap<&'static str, String>,
+    scripts: HashMap<String, &'static str>,
+}
+
+/// Scripts of every widget deriving `Widget` linked in the binary, by id: a hash of the script
+/// content shared by widgets using the same script, identical on every instance of the application
+static SCRIPT_REGISTRY: LazyLock<ScriptRegistry> = LazyLock::new(|| {
+    let mut registry = ScriptRegistry::default();
+    for widget_script in inventory::iter::<WidgetScript> {
+        let mut hasher = DefaultHasher::new();
+        widget_script.script.hash(&mut hasher);
+        let id = format!("{:016x}", hasher.finish());
+        registry.ids.insert(widget_script.widget, id.clone());
+        registry.scripts.insert(id, widget_script.script);
+    }
+    registry
+});
+
+/// Source of a widget script, to serve it from frameworks without a viewy binding
+pub fn widget_script(id: &str) -> Option<&'static str> {
+    SCRIPT_REGISTRY.scripts.get(id).copied()
 }
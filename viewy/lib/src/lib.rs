@@ -59,6 +59,8 @@
 
 #[macro_use]
 extern crate viewy_codegen;
+// Lets the code generated by `view!` refer to `::viewy` inside the crate
+extern crate self as viewy;
 extern crate figment;
 extern crate serde;
 #[doc(inline)]
@@ -70,6 +72,10 @@ use lazy_static::lazy_static;
 pub use viewy_codegen::*;
 
 pub use strum;
+#[doc(hidden)]
+pub use inventory;
+#[doc(hidden)]
+pub use crate::core::widget::{WidgetScript, WidgetStyle};
 
 mod core;
 
@@ -88,22 +94,30 @@ lazy_static! {
 }
 
 pub mod prelude {
-    pub use crate::core::component::Component;
-    pub use crate::core::config::Config;
+    pub use crate::core::assets::*;
+    pub use crate::core::cache::*;
+    pub use crate::core::component::{AsyncComponent, Component, ComponentName};
+    pub use crate::core::config::{Config, ConfigError, ConfigIssue, ContrastCheck};
+    pub use crate::core::csrf::*;
     pub use crate::core::layout::*;
     pub use crate::core::node::*;
     pub use crate::core::page::*;
     pub use crate::core::theme::*;
+    pub use crate::core::widget::{Widget, widget_script};
     pub use crate::helper_fn::*;
     pub use crate::modifiers::*;
     pub use crate::widgets::button::*;
+    pub use crate::widgets::fragment::*;
     pub use crate::widgets::icon::*;
+    pub use crate::widgets::menu::*;
     pub use crate::widgets::picker::*;
     pub use crate::widgets::select::*;
     pub use crate::widgets::sheet::*;
     pub use crate::widgets::stack::*;
     pub use crate::widgets::text::*;
+    pub use crate::widgets::theme_switcher::*;
     pub use crate::widgets::view::*;
+    pub use viewy_codegen::view;
 }
 
 #[cfg(test)]
@@ -149,6 +163,970 @@ mod tests {
         println!("{}", html);
     }
 
+    #[test]
+    fn menu_is_rendered_inline() {
+        let mut menu = Menu::new();
+        menu.append_item(MenuItem::new("Rename").shortcut("F2").clone())
+            .append_separator()
+            .append_item(MenuItem::new("Delete").destructive().clone());
+
+        let html = Page::with_title("Test")
+            .with_content(Button::new("Actions", ButtonStyle::Filled).menu(menu))
+            .compile(RenderMode::ContentOnly);
+
+        assert!(html.contains(r#"data-v-on-click="open_menu""#));
+        assert!(html.contains("<template"));
+        assert!(html.contains(r#"role="menu""#));
+        assert!(html.contains(r#"aria-keyshortcuts="F2""#));
+        assert!(html.contains("menu__item--destructive"));
+
+        let mut row = View::new();
+        row.on_contextmenu(Action::OpenMenu {
+            menu: Menu::new().append_item(MenuItem::new("Open")).clone(),
+        });
+        let html = Page::with_title("Test")
+            .with_content(row)
+            .compile(RenderMode::ContentOnly);
+        assert!(html.contains(r#"data-v-on-contextmenu="open_menu""#));
+        assert!(html.contains(r#"tabindex="0""#));
+    }
+
+    #[test]
+    fn csrf_token_is_embedded_in_page() {
+        let token = DoubleSubmitCookie.issue(None);
+        assert_eq!(DoubleSubmitCookie.issue(Some(token.as_str())), token);
+        assert!(DoubleSubmitCookie.verify(Some(token.as_str()), token.as_str()));
+        assert!(!DoubleSubmitCookie.verify(None, token.as_str()));
+
+        let html = Page::with_title("Test")
+            .with_csrf_token(token.clone())
+            .with_content(View::new())
+            .compile(RenderMode::Complete);
+
+        assert!(html.contains(&format!(
+            "<meta name='csrf-token' content='{}'>",
+            token.as_str()
+        )));
+    }
+
+    #[test]
+    fn boosted_navigation_marks_content_region() {
+        fn layout(content: Node) -> Node {
+            let mut shell = View::new();
+            shell.append_child(content);
+            shell.into()
+        }
+        let page = || {
+            Page::with_title("Test")
+                .with_layout(layout)
+                .with_boosted_navigation()
+                .with_content(View::new())
+        };
+        let layout_id = page().layout_id().to_string();
+
+        let html = page().compile(RenderMode::Complete);
+        assert!(html.contains(r#"data-v-page-content="""#));
+        assert!(html.contains(&format!(
+            "<meta name='viewy-layout' content='{layout_id}'>"
+        )));
+        assert!(html.contains("data-v-boost"));
+
+        let html = page().compile(RenderMode::LayoutOnly);
+        assert!(html.contains(r#"data-v-page-content=""><!--VIEWY_CONTENT--></div>"#));
+
+        let html = Page::with_title("Test")
+            .with_content(View::new())
+            .compile(RenderMode::Complete);
+        assert!(!html.contains("data-v-page-content"));
+    }
+
+    #[cfg(feature = "axum")]
+    #[test]
+    fn axum_pages_send_the_boosted_navigation_headers() {
+        use crate::bindings::axum::page::PageRequest;
+        use axum::extract::FromRequestParts;
+
+        let (mut parts, _) = axum::http::Request::builder()
+            .uri("/docs")
+            .header("x-viewy-render-mode", "ContentOnly")
+            .header("cookie", format!("{THEME_COOKIE}=dark"))
+            .body(())
+            .unwrap()
+            .into_parts();
+        let request =
+            futures::executor::block_on(PageRequest::from_request_parts(&mut parts, &())).unwrap();
+
+        let page = || {
+            Page::with_title("Café")
+                .with_layout(|content| content)
+                .with_boosted_navigation()
+                .with_content(View::new())
+        };
+        let response = request.respond(page());
+        let header = |name| response.headers()[name].to_str().unwrap();
+        assert_eq!(header("x-viewy-title"), "Caf%C3%A9");
+        assert_eq!(header("x-viewy-layout"), page().layout_id());
+        assert_eq!(header("content-type"), "text/html; charset=utf-8");
+    }
+
+    #[test]
+    fn layout_chain_receives_page_context() {
+        struct User(&'static str);
+
+        struct Sidebar;
+        impl Layout for Sidebar {
+            fn render(&self, content: Node, context: &PageContext) -> Node {
+                let mut sidebar = View::new();
+                sidebar.add_class("sidebar");
+                if let Some(User(name)) = context.extension::<User>() {
+                    sidebar.append_child(Text::new(name, TextStyle::Body));
+                }
+                for (label, href) in context.breadcrumbs() {
+                    let mut crumb = Text::new(&label, TextStyle::Body);
+                    if context.is_active(&href) {
+                        crumb.add_class("crumb--active");
+                    }
+                    sidebar.append_child(crumb);
+                }
+                sidebar.append_child(content);
+                sidebar.into()
+            }
+        }
+
+        fn shell(content: Node) -> Node {
+            let mut shell = View::new();
+            shell.add_class("shell");
+            shell.append_child(content);
+            shell.into()
+        }
+
+        let html = Page::with_title("Profile")
+            .with_path("/settings/profile")
+            .with_extension(User("Ada"))
+            .with_layout(shell)
+            .with_layout(Sidebar)
+            .with_content(View::new())
+            .compile(RenderMode::Complete);
+
+        assert!(html.find("shell").unwrap() < html.find("sidebar").unwrap());
+        assert!(html.contains(">Ada<"));
+        assert!(html.contains(">settings<"));
+        assert_eq!(html.matches("crumb--active").count(), 2);
+
+        let html = Page::with_title("Café")
+            .with_path("/menu/caf%C3%A9")
+            .with_layout(Sidebar)
+            .with_content(View::new())
+            .compile(RenderMode::Complete);
+        assert!(html.contains(">café<"));
+
+        let boxed = |content| {
+            let mut view = View::new();
+            view.add_class("boxed").append_child(content);
+            view.into()
+        };
+        let bare = |content| content;
+        assert_ne!(
+            Page::with_title("Boxed").with_layout(boxed).layout_id(),
+            Page::with_title("Bare").with_layout(bare).layout_id()
+        );
+    }
+
+    #[test]
+    fn fragment_renders_only_its_children() {
+        let mut stack = HStack::new(Alignment::Center);
+        stack.append_child(Fragment::from_iter([
+            Text::new("First", TextStyle::Body),
+            Text::new("Second", TextStyle::Body),
+        ]));
+        let mut icons = Fragment::new();
+        icons.append_child(Icon::new(Lucide::Check));
+
+        let html = Page::with_title("Test")
+            .with_content({
+                let mut view = View::new();
+                view.append_child(stack).append_child(icons);
+                view
+            })
+            .compile(RenderMode::ContentOnly);
+
+        assert!(html.contains(">First</p><p"));
+        assert!(html.contains("<symbol"));
+    }
+
+    #[test]
+    fn node_queries_and_transforms() {
+        let link = |href: &str| {
+            let mut node = Node {
+                node_type: NodeType::Normal("a"),
+                ..Node::default()
+            };
+            node.attributes.insert("href".to_string(), href.to_string());
+            node
+        };
+        let mut view = View::new();
+        view.append_child(Text::new("Title", TextStyle::H1))
+            .append_child(Button::new("Save", ButtonStyle::Filled))
+            .append_child(link("https://docs.rs"))
+            .append_child(link("/"));
+        let content: Node = view.into();
+
+        assert_eq!(content.iter().count(), 6);
+        assert_eq!(content.find_by_widget::<Button>().count(), 1);
+        assert_eq!(content.find_by_attribute("href", Some("/")).count(), 1);
+        assert_eq!(content.find_by_tag("h1").count(), 1);
+        assert!(content.find_by_class("text--h1").next().is_some());
+
+        let html = Page::with_title("Test")
+            .with_content(content)
+            .with_transform(|root| {
+                root.transform(
+                    |node| {
+                        node.attributes
+                            .get("href")
+                            .is_some_and(|href| href.starts_with("http"))
+                    },
+                    |link| {
+                        link.attributes
+                            .insert("rel".to_string(), "noopener".to_string());
+                    },
+                )
+            })
+            .compile(RenderMode::ContentOnly);
+        assert_eq!(html.matches(r#"rel="noopener""#).count(), 1);
+    }
+
+    #[test]
+    fn html_fragments_round_trip() {
+        let legacy = r#"<nav id="main" class="nav legacy"><a href="/?a=1&amp;b=2">Tom &amp; Jerry</a> and <b>friends</b><!-- legacy --><br><img src="/logo.svg" alt="&quot;Logo&quot;"></nav><p style="color: red">Hi</p>"#;
+        let fragment = Node::from_html(legacy);
+
+        assert_eq!(fragment.node_type, NodeType::Fragment);
+        let nav = &fragment.children[0];
+        assert_eq!(nav.html_id.as_deref(), Some("main"));
+        assert!(nav.class_list.contains("legacy"));
+        assert_eq!(nav.find_by_tag("img").next().unwrap().node_type, NodeType::SelfClosing("img"));
+        assert_eq!(nav.children[0].text.as_deref(), Some("Tom &amp; Jerry"));
+
+        let mut html = String::new();
+        fragment.clone().render(&mut html);
+        assert!(html.contains(r#"href="/?a=1&amp;b=2""#));
+        assert!(html.contains("<!-- legacy -->"));
+        assert!(Node::from_html(&html).structure_eq(&fragment));
+
+        let mut view = View::new();
+        view.append_child(Text::new("Hello", TextStyle::Body));
+        let view: Node = view.into();
+        let mut html = String::new();
+        view.clone().render(&mut html);
+        assert!(Node::from_html(&html).structure_eq(&view));
+    }
+
+    #[test]
+    fn html_fragments_keep_every_element() {
+        let fragment = Node::from_html(
+            "<div><legacy-widget>Hi</legacy-widget><center>Old <b>news</b></center></div>",
+        );
+        assert_eq!(fragment.find_by_tag("legacy-widget").count(), 1);
+        assert_eq!(
+            fragment.find_by_tag("center").next().unwrap().node_type,
+            NodeType::Element("center".to_string())
+        );
+
+        let mut html = String::new();
+        fragment.render(&mut html);
+        assert_eq!(
+            html,
+            "<div><legacy-widget>Hi</legacy-widget><center>Old <b>news</b></center></div>"
+        );
+
+        let svg = Node::from_html(
+            r#"<svg><filter id="blur"><feGaussianBlur stdDeviation="2"></feGaussianBlur></filter></svg>"#,
+        );
+        assert_eq!(svg.find_by_tag("feGaussianBlur").count(), 1);
+
+        let image = "url(data:image/png;base64,iVBORw0KGgo=)";
+        let styled = Node::from_html(&format!(
+            r#"<div style="color: rgb(0, 0, 0); background: {image}"></div>"#
+        ));
+        assert_eq!(styled.node_style["background"], image);
+        assert_eq!(styled.node_style["color"], "rgb(0, 0, 0)");
+        let unbalanced = Node::from_html(r#"<div style="background: url(a;b"></div>"#);
+        assert!(unbalanced.node_style.is_empty());
+        assert_eq!(unbalanced.attributes["style"], "background: url(a;b");
+    }
+
+    #[test]
+    fn node_tree_json_round_trip() {
+        let mut view = View::new();
+        view.append_child(Button::new("Save", ButtonStyle::Filled).icon(Lucide::Check))
+            .append_child(Node::from_html("<custom-element>Hi<!-- note --></custom-element>"));
+        let node: Node = view.into();
+
+        let json = serde_json::to_string(&VersionedNode::from(node.clone())).unwrap();
+        let restored = serde_json::from_str::<VersionedNode>(&json).unwrap();
+        assert_eq!(restored.version(), NODE_SCHEMA_VERSION);
+
+        let restored = restored.into_node();
+        assert!(restored.structure_eq(&node));
+        assert_eq!(restored.identifier, node.identifier);
+        assert_eq!(restored.find_by_widget::<Button>().count(), 1);
+        assert_eq!(
+            restored.find_by_tag("custom-element").next().unwrap().node_type,
+            NodeType::Element("custom-element".to_string())
+        );
+
+        let future = json.replacen(
+            &format!(r#""version":{NODE_SCHEMA_VERSION}"#),
+            r#""version":999"#,
+            1,
+        );
+        assert!(serde_json::from_str::<VersionedNode>(&future).is_err());
+
+        let unknown_tag = r#"{"type":{"kind":"normal","value":"blink"}}"#;
+        assert!(serde_json::from_str::<Node>(unknown_tag).is_err());
+        let element = r#"{"type":{"kind":"element","value":"blink"}}"#;
+        assert_eq!(
+            serde_json::from_str::<Node>(element).unwrap().tag(),
+            Some("blink")
+        );
+        let invalid_element = r#"{"type":{"kind":"element","value":"img src=x"}}"#;
+        assert!(serde_json::from_str::<Node>(invalid_element).is_err());
+        let comment =
+            r#"{"type":{"kind":"comment","value":"VIEWY_CONTENT"},"origin":"app::Removed"}"#;
+        let comment = serde_json::from_str::<Node>(comment).unwrap();
+        assert_eq!(comment.node_type, NodeType::Comment("VIEWY_CONTENT"));
+        assert_eq!(comment.text, None);
+        assert_eq!(comment.origin, None);
+        let comment = r#"{"type":{"kind":"comment","value":"note"},"text":" kept"}"#;
+        let comment = serde_json::from_str::<Node>(comment).unwrap();
+        assert_eq!(comment.node_type, NodeType::Comment(""));
+        assert_eq!(comment.text.as_deref(), Some("note kept"));
+    }
+
+    #[test]
+    fn page_head_metadata() {
+        let html = Page::with_title("Tom & Jerry")
+            .with_description("A cat and a mouse")
+            .with_canonical_url("https://example.com/tom-and-jerry")
+            .with_open_graph(OpenGraph::default().image("https://example.com/cover.png", "Cover"))
+            .with_twitter_card(TwitterCard::SummaryLargeImage { site: None })
+            .with_robots(Robots::none())
+            .with_lang("fr")
+            .with_dir(TextDirection::Ltr)
+            .with_stylesheet("/assets/page.css")
+            .with_content(View::new())
+            .compile(RenderMode::Complete);
+
+        assert!(html.contains("<html lang='fr' dir='ltr' class='app-theme--auto'>"));
+        assert!(html.contains("<title>Tom &amp; Jerry</title>"));
+        assert!(html.contains("<meta name='viewport' content='width=device-width, initial-scale=1.0'>"));
+        assert!(html.contains("<meta name='description' content='A cat and a mouse'>"));
+        assert!(html.contains("<meta name='robots' content='noindex, nofollow'>"));
+        assert!(html.contains("<link rel='canonical' href='https://example.com/tom-and-jerry'>"));
+        assert!(html.contains("<meta property='og:url' content='https://example.com/tom-and-jerry'>"));
+        assert!(html.contains("<meta name='twitter:image' content='https://example.com/cover.png'>"));
+        assert!(html.contains("<meta name='theme-color' media='(prefers-color-scheme: dark)'"));
+        assert!(html.contains("<link href='/assets/page.css' rel='stylesheet'>"));
+
+        assert_eq!(
+            Viewport::default()
+                .initial_scale(1.25)
+                .maximum_scale(0.75)
+                .to_string(),
+            "width=device-width, initial-scale=1.25, maximum-scale=0.75"
+        );
+    }
+
+    #[test]
+    fn component_fragment_cache() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Component, Hash)]
+        struct Sidebar {
+            role: &'static str,
+        }
+
+        impl Component for Sidebar {
+            fn render(self) -> Node {
+                RENDERS.fetch_add(1, Ordering::SeqCst);
+                let mut view = View::new();
+                view.append_child(Text::new(self.role, TextStyle::Body))
+                    .append_child(Icon::new(Lucide::Check));
+                view.into()
+            }
+        }
+
+        let cache = ComponentCache::new(LruFragmentStore::new(10));
+        let render = |node: Node| {
+            let mut html = String::new();
+            node.render(&mut html);
+            html
+        };
+
+        let miss = render(cache.render(Sidebar { role: "admin" }));
+        let hit = cache.render(Sidebar { role: "admin" });
+        assert_eq!(RENDERS.load(Ordering::SeqCst), 1);
+        assert_eq!(hit.find_by_attribute("data-v-icon-id", None).count(), 1);
+        assert_eq!(render(hit), miss);
+        assert!(miss.contains("admin"));
+
+        cache.render(Sidebar { role: "guest" });
+        assert_eq!(RENDERS.load(Ordering::SeqCst), 2);
+
+        cache.invalidate(&Sidebar { role: "admin" });
+        cache.render(Sidebar { role: "admin" });
+        cache.render(Sidebar { role: "guest" });
+        assert_eq!(RENDERS.load(Ordering::SeqCst), 3);
+
+        cache.invalidate_component::<Sidebar>();
+        cache.render(Sidebar { role: "guest" });
+        assert_eq!(RENDERS.load(Ordering::SeqCst), 4);
+
+        mod admin {
+            use crate::Component;
+            use crate::prelude::*;
+
+            #[derive(Component, Hash)]
+            pub struct Sidebar {
+                pub role: &'static str,
+            }
+
+            impl Component for Sidebar {
+                fn render(self) -> Node {
+                    Text::new("Admin sidebar", TextStyle::Body).into()
+                }
+            }
+        }
+        let admin_sidebar = render(cache.render(admin::Sidebar { role: "guest" }));
+        assert!(admin_sidebar.contains("Admin sidebar"));
+        cache.invalidate_component::<admin::Sidebar>();
+        cache.render(Sidebar { role: "guest" });
+        assert_eq!(RENDERS.load(Ordering::SeqCst), 4);
+
+        let store = LruFragmentStore::new(1);
+        let fragment = CachedFragment {
+            html: "<p></p>".to_string(),
+            icon_ids: vec![],
+            origins: vec![],
+            script_ids: vec![],
+        };
+        store.set("a", fragment.clone(), None);
+        store.set("b", fragment.clone(), None);
+        assert!(store.get("a").is_none());
+        store.set("c", fragment, Some(std::time::Duration::ZERO));
+        assert!(store.get("c").is_none());
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn async_components_are_streamed_out_of_order() {
+        use futures::StreamExt;
+        use futures::channel::oneshot;
+
+        struct Slow(oneshot::Receiver<()>);
+
+        impl AsyncComponent for Slow {
+            fn fallback(&self) -> Node {
+                Text::new("Loading", TextStyle::Body).into()
+            }
+
+            async fn render(self) -> Node {
+                self.0.await.ok();
+                Text::new("Slow", TextStyle::Body).into()
+            }
+        }
+
+        struct Fast(oneshot::Sender<()>);
+
+        impl AsyncComponent for Fast {
+            async fn render(self) -> Node {
+                self.0.send(()).ok();
+                Text::new("Fast", TextStyle::Body).into()
+            }
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        let (page, slow) = Page::with_title("Dashboard").defer(Slow(receiver));
+        let (page, fast) = page.defer(Fast(sender));
+        assert!(page.is_streamed());
+        let page = page.with_content({
+            let mut view = View::new();
+            view.append_child(slow).append_child(fast);
+            view
+        });
+
+        let chunks: Vec<String> =
+            futures::executor::block_on(page.render_stream(RenderMode::Complete).collect());
+        assert_eq!(chunks.len(), 4);
+        assert!(chunks[0].contains(&static_asset_url("js/streaming.js")));
+        assert!(chunks[0].contains("id=\"v-deferred-0\""));
+        assert!(chunks[0].contains("Loading"));
+        assert!(!chunks[0].contains("</body>"));
+        assert!(chunks[1].starts_with("<template data-v-resolved='1'>"));
+        assert!(chunks[1].contains("Fast"));
+        assert!(chunks[1].ends_with("<script>viewyResolve('1')</script>"));
+        assert!(chunks[2].contains("Slow"));
+        assert!(chunks[3].contains("</body>"));
+
+        let (page, fast) = Page::with_title("Dashboard").defer(Fast(oneshot::channel().0));
+        let chunks: Vec<String> = futures::executor::block_on(
+            page.with_content(fast)
+                .render_stream(RenderMode::ContentOnly)
+                .collect(),
+        );
+        assert_eq!(chunks.len(), 3);
+        assert!(!chunks[1].contains("<script>"));
+    }
+
+    #[test]
+    fn view_macro_builds_the_same_tree() {
+        #[derive(Component)]
+        struct Badge {
+            label: &'static str,
+        }
+
+        impl Component for Badge {
+            fn render(self) -> Node {
+                Text::new(self.label, TextStyle::Caption).into()
+            }
+        }
+
+        let show_cancel = false;
+        let items = ["One", "Two"];
+        let built = view! {
+            <VStack(Alignment::Stretch) gap={vec![scale(4)]} add_class("list")>
+                <Text("Title", TextStyle::H1) set_attr("aria-level", "1")/>
+                for item in items {
+                    <Button(item, ButtonStyle::Flat) on_click={Action::CloseParentWindow}/>
+                }
+                if show_cancel {
+                    <Button("Cancel", ButtonStyle::Outlined)/>
+                } else {
+                    <Badge { label: "Read only" }/>
+                }
+                {Text::new("Footer", TextStyle::Body)}
+            </VStack>
+        };
+
+        let mut expected = VStack::new(Alignment::Stretch);
+        expected
+            .gap(vec![scale(4)])
+            .add_class("list")
+            .append_child(Text::new("Title", TextStyle::H1).set_attr("aria-level", "1"));
+        for item in items {
+            expected.append_child(
+                Button::new(item, ButtonStyle::Flat).on_click(Action::CloseParentWindow),
+            );
+        }
+        expected
+            .append_child(Badge { label: "Read only" })
+            .append_child(Text::new("Footer", TextStyle::Body));
+        assert!(built.structure_eq(&expected.into()));
+
+        let fragment = view! {
+            <Text("A", TextStyle::Body)/>
+            <Text("B", TextStyle::Body)/>
+        };
+        assert_eq!(fragment.node_type, NodeType::Fragment);
+        assert_eq!(fragment.children.len(), 2);
+    }
+
+    #[test]
+    fn derived_component_props_builder() {
+        #[derive(Component)]
+        #[component(name = "KPI card")]
+        struct Card {
+            #[prop(into)]
+            title: String,
+            #[prop(default = 2)]
+            columns: u8,
+            #[prop(optional, into)]
+            subtitle: Option<String>,
+            #[children]
+            children: Vec<Node>,
+        }
+
+        impl Component for Card {
+            fn render(self) -> Node {
+                let mut card = VStack::new(Alignment::Stretch);
+                card.append_child(Text::new(&self.title, TextStyle::H2))
+                    .add_class(&format!("card--{}-columns", self.columns));
+                if let Some(subtitle) = &self.subtitle {
+                    card.append_child(Text::new(subtitle, TextStyle::Caption));
+                }
+                card.children.extend(self.children);
+                card.into()
+            }
+        }
+
+        assert_eq!(Card::name(), "KPI card");
+
+        #[derive(Component)]
+        struct Plain {}
+
+        impl Component for Plain {
+            fn render(self) -> Node {
+                View::new().into()
+            }
+        }
+
+        assert_eq!(Plain::name(), "viewy::tests::Plain");
+
+        let card = Card::builder()
+            .title("Sales")
+            .subtitle("This month")
+            .child(Text::new("12 000 €", TextStyle::Body))
+            .build();
+        assert_eq!(card.columns, 2);
+        assert_eq!(card.subtitle.as_deref(), Some("This month"));
+        assert_eq!(card.children.len(), 1);
+
+        let built = view! {
+            <Card { title: "Sales".to_string(), columns: 3, subtitle: None, children: vec![] }>
+                <Text("12 000 €", TextStyle::Body)/>
+            </Card>
+        };
+        let expected: Node = Card::builder()
+            .title("Sales")
+            .columns(3)
+            .child(Text::new("12 000 €", TextStyle::Body))
+            .into();
+        assert!(built.structure_eq(&expected));
+        assert_eq!(built.find_by_class("card--3-columns").count(), 1);
+    }
+
+    #[test]
+    fn widget_scripts_are_preloaded_when_used() {
+        let script_id = Picker::script_id().unwrap();
+        assert_eq!(Select::script_id(), Some(script_id.clone()));
+        assert!(Button::script_id().is_none());
+        assert!(widget_script(&script_id).unwrap().contains("export function init"));
+
+        let html = Page::with_title("Test")
+            .with_content({
+                let mut view = View::new();
+                view.append_child(Picker::new("size", "m", PickerStyle::Segmented))
+                    .append_child(Select::new("color", "red"));
+                view
+            })
+            .compile(RenderMode::Complete);
+        let preload = format!(
+            "<link rel='modulepreload' href='{}'>",
+            widget_script_url(&script_id)
+        );
+        assert_eq!(html.matches(&preload).count(), 1);
+        assert!(html.contains(&format!(r#"data-v-script="{script_id}""#)));
+
+        let html = Page::with_title("Test")
+            .with_content(Button::new("Ok", ButtonStyle::Filled))
+            .compile(RenderMode::Complete);
+        assert!(!html.contains("modulepreload"));
+    }
+
+    #[test]
+    fn every_widget_style_is_registered() {
+        use crate::widgets::{form::Form, get_all_stylesheet, header::Header};
+
+        let styles = get_all_stylesheet();
+        assert!(styles.contains(&Form::STYLE));
+        assert!(styles.contains(&Header::STYLE));
+        assert!(styles.contains(&Button::STYLE));
+        assert_eq!(styles, get_all_stylesheet());
+    }
+
+    #[test]
+    fn critical_css_only_contains_used_widgets() {
+        assert_eq!(Button::widget_name(), "Button");
+
+        let html = Page::with_title("Test")
+            .with_critical_css()
+            .with_content(Button::new("Ok", ButtonStyle::Filled))
+            .compile(RenderMode::Complete);
+        let critical_css = html
+            .split_once("<style>")
+            .and_then(|(_, rest)| rest.split_once("</style>"))
+            .map(|(critical_css, _)| critical_css)
+            .unwrap();
+        assert!(critical_css.contains(".button"));
+        assert!(!critical_css.contains(".tab-container"));
+        assert!(html.contains(&format!(
+            "rel='preload' href='{}' as='style'",
+            app_stylesheet_url()
+        )));
+
+        // Every widget style compiles on its own
+        let widgets = inventory::iter::<crate::WidgetStyle>
+            .into_iter()
+            .map(|widget_style| widget_style.widget);
+        assert!(get_critical_stylesheet(widgets).contains(".tab-container"));
+    }
+
+    #[test]
+    fn cached_and_deferred_widgets_get_critical_css() {
+        use futures::StreamExt;
+
+        #[derive(Component, Hash)]
+        struct SizePicker;
+
+        impl Component for SizePicker {
+            fn render(self) -> Node {
+                Picker::new("size", "m", PickerStyle::Segmented).into()
+            }
+        }
+
+        struct DeferredPicker;
+
+        impl AsyncComponent for DeferredPicker {
+            async fn render(self) -> Node {
+                Picker::new("size", "m", PickerStyle::Segmented).into()
+            }
+        }
+
+        let preload = format!(
+            "<link rel='modulepreload' href='{}'>",
+            widget_script_url(&Picker::script_id().unwrap())
+        );
+        let page = || {
+            Page::with_title("Test")
+                .with_critical_css()
+                .with_content(SizePicker.cached())
+                .compile(RenderMode::Complete)
+        };
+        let miss = page();
+        let hit = page();
+        for html in [miss, hit] {
+            assert!(html.contains(".picker"));
+            assert!(html.contains(&preload));
+        }
+
+        let (page, picker) = Page::with_title("Test")
+            .with_critical_css()
+            .defer(DeferredPicker);
+        let chunks: Vec<String> = futures::executor::block_on(
+            page.with_content(picker)
+                .render_stream(RenderMode::Complete)
+                .collect(),
+        );
+        assert!(!chunks[0].contains(".picker"));
+        assert!(chunks[1].starts_with("<style>"));
+        assert!(chunks[1].contains(".picker"));
+        assert!(chunks[1].contains(&preload));
+    }
+
+    #[test]
+    fn asset_urls_are_fingerprinted() {
+        let stylesheet = app_stylesheet();
+        assert_eq!(
+            app_stylesheet_url(),
+            format!("/viewy-static/app.{}.css", stylesheet.hash)
+        );
+        assert!(std::ptr::eq(stylesheet, app_stylesheet()));
+
+        let html = Page::with_title("Test")
+            .with_content(Button::new("Ok", ButtonStyle::Filled))
+            .compile(RenderMode::Complete);
+        assert!(html.contains(&format!(
+            "<link href='{}' rel='stylesheet'>",
+            app_stylesheet_url()
+        )));
+        for asset in ["js/importmap.js", "js/app.js", "css/static.css"] {
+            let url = static_asset_url(asset);
+            assert!(url.starts_with(&format!("/viewy-static/{STATIC_VERSION}/")));
+            assert!(html.contains(&url));
+        }
+        assert!(!html.contains("/viewy-static/app.css"));
+    }
+
+    #[test]
+    fn user_styles_are_compiled_with_widget_styles() {
+        use crate::core::config::StyleSettings;
+
+        let directory = std::env::temp_dir().join(format!("viewy-styles-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(directory.join("pages")).unwrap();
+        std::fs::write(directory.join("_tokens.scss"), "$card-gap: 16;").unwrap();
+        std::fs::write(
+            directory.join("pages/card.scss"),
+            "@import 'tokens'; .card { padding: sp($card-gap); border-color: var(--accent); }",
+        )
+        .unwrap();
+
+        let stylesheet = compile_stylesheet(&Config {
+            styles: StyleSettings {
+                sources: vec![directory.clone()],
+            },
+            ..Config::default()
+        });
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert!(stylesheet.contains(".card{padding:1rem;border-color:var(--accent)}"));
+        assert!(stylesheet.contains("--accent:"));
+        assert!(stylesheet.contains(".button"));
+    }
+
+    #[test]
+    fn config_errors_point_at_their_key() {
+        use crate::core::config::HexColor;
+        use figment::Figment;
+        use figment::providers::{Format, Toml};
+
+        assert_eq!(
+            "#abc".parse::<HexColor>().unwrap().0,
+            [0xaa, 0xbb, 0xcc, 0xff]
+        );
+        assert!("#0052zz".parse::<HexColor>().is_err());
+        assert!(Config::default().validate().is_ok());
+
+        let figment = |toml: &str| Figment::from(Config::default()).merge(Toml::string(toml));
+
+        let malformed_color = "[colors]\naccent = { light = \"#0052zz\", dark = \"#3385ff\" }";
+        let err = Config::try_from_figment(&figment(malformed_color)).unwrap_err();
+        assert!(matches!(err, ConfigError::Parse(_)));
+        assert!(err.to_string().contains("colors.accent.light"));
+
+        let err = Config::try_from_figment(&figment(
+            "[shapes]\nborder-radius = -2\n[app]\nfavicons = [{ rel = \"icon\", href = \"favicon.svg\" }]",
+        ))
+        .unwrap_err();
+        let ConfigError::Invalid(issues) = err else {
+            panic!("expected validation issues, got {err}");
+        };
+        let keys = issues
+            .iter()
+            .map(|issue| issue.key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["shapes.border-radius", "app.favicons[0].href"]);
+        assert!(issues[0].source.as_deref().unwrap().contains("TOML"));
+
+        let unknown = Config::unknown_keys(&figment("[shapes]\nborder-radus = 4\n[icon-packs]"));
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].key, "shapes.border-radus");
+    }
+
+    #[test]
+    fn pages_are_themed_with_their_config() {
+        let brand = || {
+            let mut brand = Config::default();
+            brand.colors.accent.light = "#ff0000".into();
+            brand.shapes.border_radius = 0;
+            brand
+        };
+
+        assert!(config_stylesheet(&brand()).is_none());
+        let stylesheet = register_config_stylesheet(&brand());
+        let html = Page::with_title("Test")
+            .with_config(brand())
+            .with_critical_css()
+            .with_content(Button::new("Ok", ButtonStyle::Filled))
+            .compile(RenderMode::Complete);
+
+        assert_ne!(stylesheet.url(), app_stylesheet_url());
+        assert!(html.contains(&format!("rel='preload' href='{}'", stylesheet.url())));
+        assert!(html.contains("--accent: light-dark(#ff0000ff"));
+        assert!(stylesheet.css.contains("--accent: light-dark(#ff0000ff"));
+        assert!(
+            !app_stylesheet()
+                .css
+                .contains("--accent: light-dark(#ff0000ff")
+        );
+        assert!(std::sync::Arc::ptr_eq(
+            &stylesheet,
+            &stylesheet_by_file_name(&stylesheet.file_name()).unwrap()
+        ));
+        assert!(std::sync::Arc::ptr_eq(
+            &stylesheet,
+            &config_stylesheet(&brand()).unwrap()
+        ));
+
+        let html = Page::with_title("Test")
+            .with_content(Button::new("Ok", ButtonStyle::Filled))
+            .compile(RenderMode::Complete);
+        assert!(html.contains(&app_stylesheet_url()));
+
+        let mut unregistered = Config::default();
+        unregistered.colors.accent.light = "#0000ff".into();
+        assert!(config_stylesheet(&unregistered).is_none());
+        let html = Page::with_title("Test")
+            .with_config(unregistered.clone())
+            .with_content(View::new())
+            .compile(RenderMode::Complete);
+        let stylesheet = config_stylesheet(&unregistered).unwrap();
+        assert!(html.contains(&stylesheet.url()));
+    }
+
+    #[cfg(feature = "rocket")]
+    #[test]
+    fn rocket_serves_registered_config_stylesheets_only() {
+        use crate::bindings::rocket::static_assets::{
+            viewy_config_stylesheets_fairing, viewy_static_assets_fairing,
+        };
+        use rocket::http::Status;
+        use rocket::local::blocking::Client;
+
+        let mut brand = Config::default();
+        brand.colors.accent.light = "#00ff00".into();
+        let client = Client::tracked(
+            rocket::build()
+                .attach(viewy_static_assets_fairing())
+                .attach(viewy_config_stylesheets_fairing(vec![brand])),
+        )
+        .unwrap();
+
+        let mut brand = Config::default();
+        brand.colors.accent.light = "#00ff00".into();
+        let stylesheet = config_stylesheet(&brand).unwrap();
+        let response = client.get(stylesheet.url()).dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.into_string().unwrap().contains("#00ff00"));
+
+        let unknown = client
+            .get("/viewy-static/app.0123456789abcdef.css")
+            .dispatch();
+        assert_eq!(unknown.status(), Status::NotFound);
+        let unversioned = client.get("/viewy-static/app.css").dispatch();
+        assert_eq!(unversioned.status(), Status::SeeOther);
+    }
+
+    #[test]
+    fn tonal_palettes_derive_from_key_colors() {
+        use crate::core::config::HexColor;
+        use palette::color_difference::Wcag21RelativeContrast;
+        use strum::IntoEnumIterator;
+
+        let accent: HexColor = "#0052cc".into();
+        assert_eq!(tone(accent, 0).0, [0, 0, 0, 255]);
+        assert_eq!(tone(accent, 100).0, [255, 255, 255, 255]);
+        let luminances = TONES
+            .iter()
+            .map(|value| {
+                let color: palette::Srgba<u8> = tone(accent, *value).into();
+                color.color.into_format::<f32>().relative_luminance().luma
+            })
+            .collect::<Vec<f32>>();
+        assert!(luminances.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let mut config = Config::default();
+        config.colors.secondary = Some("#5b5f71".into());
+        assert_eq!(
+            Color::Tone(KeyColor::Secondary, 42).as_str(),
+            "--secondary-40"
+        );
+        assert_eq!(
+            Color::Tone(KeyColor::Secondary, 42)
+                .get_hex_color_from(&config, &Theme::Light)
+                .to_string(),
+            tone("#5b5f71".into(), 40).to_string()
+        );
+        assert!(Color::iter().all(|color| !matches!(color, Color::Tone(..))));
+
+        let stylesheet = compile_stylesheet(&config);
+        for key_color in KeyColor::iter() {
+            assert!(stylesheet.contains(&format!("--{}-95:", key_color.as_str())));
+        }
+    }
+
     #[test]
     fn compile_styles() {
         let stylesheets = crate::widgets::get_all_stylesheet().join("");
@@ -165,4 +1143,281 @@ mod tests {
             panic!("Error during scss compilation")
         }
     }
+    #[test]
+    fn contrast_audit_reports_violations_with_suggestions() {
+        use crate::core::config::{AccessibilitySettings, Colors, ThemedColor};
+
+        let config = Config {
+            colors: Colors {
+                destructive: ThemedColor {
+                    light: "#ff9999".into(),
+                    dark: "#ff0048".into(),
+                },
+                ..Config::default().colors
+            },
+            ..Config::default()
+        };
+        let violations = audit_contrast(&config);
+        let violation = violations
+            .iter()
+            .find(|violation| {
+                matches!(violation.theme, Theme::Light)
+                    && matches!(violation.pairing.foreground, Color::Destructive)
+                    && matches!(violation.pairing.background, Color::Background)
+            })
+            .expect("light destructive text on the background is reported");
+        assert!(violation.ratio < AA_TEXT_CONTRAST);
+        assert!(contrast_ratio(violation.suggestion, violation.background) >= AA_TEXT_CONTRAST);
+        assert!(violation.to_string().contains("try #"));
+
+        let checked = Config {
+            accessibility: AccessibilitySettings {
+                contrast_check: ContrastCheck::Error,
+            },
+            ..config
+        };
+        assert!(std::panic::catch_unwind(|| compile_stylesheet(&checked)).is_err());
+    }
+
+    #[test]
+    fn high_contrast_palette_reaches_aaa() {
+        use crate::widgets::tabs::TabContainer;
+
+        let config = Config::default();
+        for theme in [Theme::Light, Theme::Dark] {
+            for pairing in WIDGET_PAIRINGS.iter().filter(|pairing| !pairing.disabled) {
+                let ratio = contrast_ratio(
+                    high_contrast_color(&config, pairing.foreground, &theme),
+                    high_contrast_color(&config, pairing.background, &theme),
+                );
+                let expected = if pairing.minimum_ratio >= AA_TEXT_CONTRAST {
+                    AAA_TEXT_CONTRAST
+                } else {
+                    AA_TEXT_CONTRAST
+                };
+                assert!(
+                    ratio >= expected,
+                    "{pairing:?} in {} theme: {ratio}",
+                    theme.as_str()
+                );
+            }
+        }
+        assert_eq!(
+            Color::Accent
+                .get_hex_color_from(&config, &Theme::HighContrast)
+                .0,
+            high_contrast_color(&config, Color::Accent, &Theme::Light).0
+        );
+
+        let stylesheet = compile_stylesheet(&config);
+        assert!(stylesheet.contains("@media (prefers-contrast: more)"));
+        assert!(stylesheet.contains(".app-theme--high-contrast"));
+        assert!(stylesheet.contains("@media (forced-colors: active)"));
+        assert!(stylesheet.contains("Highlight"));
+        for style in [
+            Button::STYLE,
+            Menu::STYLE,
+            Picker::STYLE,
+            Select::STYLE,
+            TabContainer::STYLE,
+        ] {
+            assert!(style.contains("@media (forced-colors: active)"));
+            assert!(style.contains("Highlight"));
+        }
+    }
+
+    #[test]
+    fn widget_color_pairs_are_audited() {
+        use crate::widgets::get_all_stylesheet;
+
+        // Colors set by a rule, inherited by its nested rules; `None` when not a palette variable
+        struct Rule {
+            color: Option<String>,
+            background: Option<String>,
+            sets_color: bool,
+            sets_background: bool,
+            same_element: bool,
+        }
+        fn variable(value: &str) -> Option<String> {
+            let value = value.trim();
+            value
+                .strip_prefix("var(")
+                .and_then(|value| value.strip_suffix(')'))
+                .filter(|name| name.starts_with("--") && !name.contains(['(', ',', ' ']))
+                .map(str::to_string)
+        }
+        fn color_pairs(style: &str, pairs: &mut Vec<(String, String)>) {
+            let mut style = style.to_string();
+            while let Some(start) = style.find("/*") {
+                let end = style[start..]
+                    .find("*/")
+                    .map_or(style.len(), |end| start + end + 2);
+                style.replace_range(start..end, "");
+            }
+            let style = style
+                .lines()
+                .map(|line| match line.find("//") {
+                    Some(start)
+                        if line[..start].trim().is_empty() || line[..start].ends_with(' ') =>
+                    {
+                        &line[..start]
+                    }
+                    _ => line,
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let mut rules = vec![Rule {
+                color: None,
+                background: None,
+                sets_color: false,
+                sets_background: false,
+                same_element: false,
+            }];
+            let mut buffer = String::new();
+            let declare = |rules: &mut Vec<Rule>, declaration: &str| {
+                let Some((property, value)) = declaration.split_once(':') else {
+                    return;
+                };
+                let rule = rules.last_mut().unwrap();
+                match property.trim() {
+                    "color" => {
+                        rule.color = variable(value);
+                        rule.sets_color = true;
+                    }
+                    "background" | "background-color" => {
+                        rule.background = variable(value);
+                        rule.sets_background = true;
+                    }
+                    _ => {}
+                }
+            };
+            for character in style.chars() {
+                match character {
+                    '{' => {
+                        let selector = buffer.trim();
+                        let parent = rules.last().unwrap();
+                        // States and modifiers of the parent element, and at-rules, style the same box
+                        let same_element = ["&:", "&--", "&.", "&[", "@"]
+                            .iter()
+                            .any(|prefix| selector.starts_with(prefix));
+                        rules.push(Rule {
+                            color: parent.color.clone(),
+                            background: parent.background.clone(),
+                            sets_color: false,
+                            sets_background: false,
+                            same_element,
+                        });
+                        buffer.clear();
+                    }
+                    ';' => {
+                        declare(&mut rules, &buffer);
+                        buffer.clear();
+                    }
+                    '}' => {
+                        declare(&mut rules, &buffer);
+                        buffer.clear();
+                        let rule = rules.pop().unwrap();
+                        if !(rule.sets_color || rule.sets_background && rule.same_element) {
+                            continue;
+                        }
+                        if let (Some(color), Some(background)) = (rule.color, rule.background)
+                            && !pairs.contains(&(color.clone(), background.clone()))
+                        {
+                            pairs.push((color, background));
+                        }
+                    }
+                    _ => buffer.push(character),
+                }
+            }
+        }
+
+        let mut pairs = vec![];
+        for style in get_all_stylesheet() {
+            color_pairs(style, &mut pairs);
+        }
+        assert!(pairs.contains(&("--accent".to_string(), "--accentuated-surface".to_string())));
+        for (color, background) in pairs {
+            assert!(
+                WIDGET_PAIRINGS.iter().any(|pairing| {
+                    pairing.foreground.as_str() == color
+                        && pairing.background.as_str() == background
+                }),
+                "{color} on {background} is used by a widget but missing from WIDGET_PAIRINGS"
+            );
+        }
+
+        assert!(
+            audit_contrast(&Config::default())
+                .iter()
+                .all(|violation| !matches!(violation.theme, Theme::HighContrast))
+        );
+    }
+
+    #[test]
+    fn theme_switcher_choice_is_rendered() {
+        assert_eq!("dark".parse::<Theme>(), Ok(Theme::Dark));
+        assert!("sepia".parse::<Theme>().is_err());
+
+        let html = Page::with_title("Test")
+            .with_theme(Theme::Dark)
+            .with_content(ThemeSwitcher::new())
+            .compile(RenderMode::Complete);
+        assert!(html.contains("<html class='app-theme--dark'"));
+        assert!(html.contains("<meta name='color-scheme' content='dark'>"));
+        assert!(html.contains(&format!("name=\"{THEME_COOKIE}\"")));
+        assert!(html.contains("picker--segmented"));
+        assert_eq!(html.matches("data-v-script").count(), 2);
+        assert!(app_stylesheet().css.contains(".app-theme--dark"));
+        assert!(!html.contains("value=\"high-contrast\""));
+        let accent = &crate::CONFIG.colors.accent;
+        assert!(html.contains(&format!(
+            "<meta name='theme-color' media='(prefers-color-scheme: light)' content='{}' data-v-light='{}'",
+            accent.dark.to_string(),
+            accent.light.to_string()
+        )));
+        assert!(html.contains("data-v-high-contrast='#"));
+
+        let html = Page::with_title("Test")
+            .with_content({
+                let mut switcher = ThemeSwitcher::new();
+                switcher.high_contrast("More contrast");
+                switcher
+            })
+            .compile(RenderMode::Complete);
+        assert!(html.contains("value=\"high-contrast\""));
+        assert!(html.contains("More contrast"));
+    }
+
+    #[cfg(feature = "rocket")]
+    #[rocket::get("/")]
+    fn auto() -> Page<'static> {
+        Page::with_title("Auto")
+    }
+
+    #[cfg(feature = "rocket")]
+    #[rocket::get("/light")]
+    fn light() -> Page<'static> {
+        Page::with_title("Light").with_theme(Theme::Light)
+    }
+
+    #[cfg(feature = "rocket")]
+    #[test]
+    fn rocket_pages_use_the_theme_cookie() {
+        use rocket::http::Cookie;
+        use rocket::local::blocking::Client;
+
+        let client =
+            Client::tracked(rocket::build().mount("/", rocket::routes![auto, light])).unwrap();
+        let html = |path: &'static str| {
+            client
+                .get(path)
+                .cookie(Cookie::new(THEME_COOKIE, "dark"))
+                .dispatch()
+                .into_string()
+                .unwrap()
+        };
+        assert!(html("/").contains("class='app-theme--dark'"));
+        assert!(html("/light").contains("class='app-theme--light'"));
+    }
 }
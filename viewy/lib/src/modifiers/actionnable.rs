@@ -1,7 +1,62 @@
 use crate::bindings::uri::Uri;
-use crate::prelude::SheetEdge;
+use crate::prelude::{Menu, SheetEdge};
 use crate::{core::widget::Widget, node::NodeType};
+use html_escape::encode_double_quoted_attribute;
 use short_uuid::short;
+use std::fmt::Display;
+
+/// HTTP method used by [`Action::Request`]
+#[derive(Debug, Clone, Copy)]
+pub enum RequestMethod {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl Display for RequestMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RequestMethod::Get => write!(f, "GET"),
+            RequestMethod::Post => write!(f, "POST"),
+            RequestMethod::Put => write!(f, "PUT"),
+            RequestMethod::Patch => write!(f, "PATCH"),
+            RequestMethod::Delete => write!(f, "DELETE"),
+        }
+    }
+}
+
+/// Body sent by [`Action::Request`]
+pub enum RequestBody<'a> {
+    Empty,
+    /// Send the fields of the form with this id
+    Form(&'a str),
+    /// Send a JSON document
+    Json(String),
+}
+
+/// What to do once the request of an [`Action::Request`] succeeded.
+///
+/// If the request fails, an error toast is displayed instead. In both cases, the message of the
+/// toast can be set by the server with the `x-viewy-toast` response header.
+pub enum AfterRequest<'a> {
+    Navigate {
+        url: Uri,
+    },
+    /// Navigate to the url the server redirected the request to
+    FollowRedirect,
+    /// Reload the whole page
+    Reload,
+    /// Reload the content of the element with the id `region_id`
+    ReloadRegion {
+        region_id: &'a str,
+        content_url: Uri,
+    },
+    ShowToast {
+        message: &'a str,
+    },
+}
 
 /// Describe the different actions that will be triggered
 pub enum Action<'a> {
@@ -26,6 +81,59 @@ pub enum Action<'a> {
         form_name: &'a str,
         inject_into: Option<&'a str>,
     },
+    /// Open a menu rendered inline within the widget, without any network round trip
+    OpenMenu {
+        menu: Menu,
+    },
+    /// Send a request with any method, including the page CSRF token, then act on the response.
+    /// ```rust,no_run
+    /// # use viewy::prelude::*;
+    /// # use viewy::bindings::uri::Uri;
+    /// # fn archive_button(archive_url: Uri, project_list_url: Uri) {
+    /// Button::new("Archive", ButtonStyle::Outlined)
+    ///     .on_click(Action::Request {
+    ///         method: RequestMethod::Post,
+    ///         url: archive_url,
+    ///         body: RequestBody::Empty,
+    ///         then: Box::new(AfterRequest::ReloadRegion {
+    ///             region_id: "projects",
+    ///             content_url: project_list_url,
+    ///         }),
+    ///     });
+    /// # }
+    /// ```
+    Request {
+        method: RequestMethod,
+        url: Uri,
+        body: RequestBody<'a>,
+        then: Box<AfterRequest<'a>>,
+    },
+    /// Ask the user to confirm before running the wrapped `action`.
+    ///
+    /// The confirmation popup is built client side, so it doesn't need any network round trip.
+    /// ```rust,no_run
+    /// # use viewy::prelude::*;
+    /// # fn delete_button(url: viewy::bindings::uri::Uri) {
+    /// Button::new("Delete", ButtonStyle::Filled)
+    ///     .destructive()
+    ///     .on_click(Action::Confirm {
+    ///         title: "Delete this project?",
+    ///         message: "This action cannot be undone.",
+    ///         confirm_label: "Delete",
+    ///         cancel_label: "Cancel",
+    ///         destructive: true,
+    ///         action: Box::new(Action::Navigate { url }),
+    ///     });
+    /// # }
+    /// ```
+    Confirm {
+        title: &'a str,
+        message: &'a str,
+        confirm_label: &'a str,
+        cancel_label: &'a str,
+        destructive: bool,
+        action: Box<Action<'a>>,
+    },
 }
 
 impl Action<'_> {
@@ -116,6 +224,132 @@ impl Action<'_> {
                     .attributes
                     .insert("data-v-url".to_string(), sheet_content_url.to_string());
             }
+            Action::OpenMenu { menu } => {
+                let menu_name = short!();
+                let template_id = format!("menu_template_{}", menu_name);
+                widget
+                    .attributes
+                    .insert(format!("data-v-on-{event}"), "open_menu".to_string());
+                widget.attributes.insert(
+                    "data-v-target-menu".to_string(),
+                    format!("menu_{}", menu_name),
+                );
+                widget
+                    .attributes
+                    .insert("data-v-menu-template".to_string(), template_id.clone());
+                if event == "contextmenu" {
+                    // Focusable so the context menu key can open the menu
+                    widget
+                        .attributes
+                        .entry("tabindex".to_string())
+                        .or_insert_with(|| "0".to_string());
+                }
+                widget
+                    .children
+                    .push(menu.clone().into_template(&template_id));
+            }
+            Action::Request {
+                method,
+                url,
+                body,
+                then,
+            } => {
+                widget
+                    .attributes
+                    .insert(format!("data-v-on-{event}"), "request".to_string());
+                widget
+                    .attributes
+                    .insert("data-v-method".to_string(), method.to_string());
+                widget
+                    .attributes
+                    .insert("data-v-url".to_string(), url.to_string());
+                match body {
+                    RequestBody::Empty => {}
+                    RequestBody::Form(form_id) => {
+                        widget
+                            .attributes
+                            .insert("data-v-body-form".to_string(), form_id.to_string());
+                    }
+                    RequestBody::Json(json) => {
+                        widget.attributes.insert(
+                            "data-v-body-json".to_string(),
+                            encode_double_quoted_attribute(json).to_string(),
+                        );
+                    }
+                }
+                match then.as_ref() {
+                    AfterRequest::Navigate { url } => {
+                        widget
+                            .attributes
+                            .insert("data-v-then".to_string(), "navigate".to_string());
+                        widget
+                            .attributes
+                            .insert("data-v-then-url".to_string(), url.to_string());
+                    }
+                    AfterRequest::FollowRedirect => {
+                        widget
+                            .attributes
+                            .insert("data-v-then".to_string(), "follow_redirect".to_string());
+                    }
+                    AfterRequest::Reload => {
+                        widget
+                            .attributes
+                            .insert("data-v-then".to_string(), "reload".to_string());
+                    }
+                    AfterRequest::ReloadRegion {
+                        region_id,
+                        content_url,
+                    } => {
+                        widget
+                            .attributes
+                            .insert("data-v-then".to_string(), "reload_region".to_string());
+                        widget
+                            .attributes
+                            .insert("data-v-then-region".to_string(), region_id.to_string());
+                        widget
+                            .attributes
+                            .insert("data-v-then-url".to_string(), content_url.to_string());
+                    }
+                    AfterRequest::ShowToast { message } => {
+                        widget
+                            .attributes
+                            .insert("data-v-then".to_string(), "show_toast".to_string());
+                        widget.attributes.insert(
+                            "data-v-then-message".to_string(),
+                            encode_double_quoted_attribute(message).to_string(),
+                        );
+                    }
+                }
+            }
+            Action::Confirm {
+                title,
+                message,
+                confirm_label,
+                cancel_label,
+                destructive,
+                action,
+            } => {
+                action.apply(event, widget);
+                widget
+                    .attributes
+                    .insert("data-v-confirm-event".to_string(), event.to_string());
+                for (name, value) in [
+                    ("data-v-confirm-title", title),
+                    ("data-v-confirm-message", message),
+                    ("data-v-confirm-label", confirm_label),
+                    ("data-v-cancel-label", cancel_label),
+                ] {
+                    widget.attributes.insert(
+                        name.to_string(),
+                        encode_double_quoted_attribute(value).to_string(),
+                    );
+                }
+                if *destructive {
+                    widget
+                        .attributes
+                        .insert("data-v-confirm-destructive".to_string(), "true".to_string());
+                }
+            }
             Action::SubmitForm { form_name, .. } => {}
             Action::CloseParentWindow => {
                 widget.attributes.insert(
@@ -136,6 +370,11 @@ pub trait OnClickActionnable: Widget {
         action.apply("dblclick", self);
         self
     }
+    /// Triggered by a right click or the context menu key, usually with [`Action::OpenMenu`]
+    fn on_contextmenu(&mut self, action: Action) -> &mut Self {
+        action.apply("contextmenu", self);
+        self
+    }
 }
 
 pub trait KeyboardActionnable: Widget {
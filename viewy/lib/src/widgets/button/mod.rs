@@ -1,7 +1,8 @@
 use crate::core::node::{Node, NodeType};
 use crate::core::widget::Widget;
 use crate::modifiers::{Attributable, Classable, OnClickActionnable};
-use crate::prelude::{Icon, IconPack, Text, TextStyle};
+use crate::modifiers::Action;
+use crate::prelude::{Icon, IconPack, Menu, Text, TextStyle};
 
 /// Used to set a button's importance level.
 #[derive(Debug, Clone)]
@@ -79,6 +80,23 @@ impl Button {
         self
     }
 
+    /// Attach a menu that opens when the button is clicked
+    /// ```rust,no_run
+    /// # use viewy::prelude::*;
+    /// Button::new("Options", ButtonStyle::Outlined)
+    ///     .menu({
+    ///         let mut menu = Menu::new();
+    ///         menu.append_item(MenuItem::new("Duplicate").clone());
+    ///         menu
+    ///     });
+    /// ```
+    pub fn menu(&mut self, menu: Menu) -> &mut Self {
+        self.set_attr("aria-haspopup", "menu")
+            .set_attr("aria-expanded", "false");
+        Action::OpenMenu { menu }.apply("click", self);
+        self
+    }
+
     fn render(&mut self) {
         let style = self.style.clone();
         self.add_class("button")
@@ -3,7 +3,6 @@ use crate::modifiers::{Appendable, Attributable, Classable, Cardifiable};
 use crate::core::node::Node;
 use crate::core::widget::Widget;
 use crate::node::NodeType;
-use rocket::uri;
 
 pub enum FormMethod {
     Get,
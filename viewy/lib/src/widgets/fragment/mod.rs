@@ -0,0 +1,59 @@
+use crate::core::node::{Node, NodeType};
+use crate::core::widget::Widget;
+use crate::modifiers::Appendable;
+
+/// Group several widgets without wrapping them in an element.
+///
+/// Only the children of a fragment are rendered, so a component can return siblings
+/// that stay direct children of a flex or grid container, or of a table.
+/// ```rust,no_run
+/// # use viewy::prelude::*;
+/// # use viewy::Component;
+/// #[derive(Component)]
+/// struct Actions;
+///
+/// impl Component for Actions {
+///     fn render(self) -> Node {
+///         let mut fragment = Fragment::new();
+///         fragment
+///             .append_child(Button::new("Cancel", ButtonStyle::Outlined))
+///             .append_child(Button::new("Save", ButtonStyle::Filled));
+///         fragment.into()
+///     }
+/// }
+/// ```
+#[derive(Widget, Appendable)]
+#[widget(style = "./style.scss")]
+pub struct Fragment {
+    pub node: Node,
+}
+
+impl Fragment {
+    pub fn new() -> Self {
+        Fragment {
+            node: Node {
+                node_type: NodeType::Fragment,
+                ..Node::default()
+            },
+        }
+    }
+
+    pub fn render(&mut self) {}
+}
+
+impl Default for Fragment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> FromIterator<C> for Fragment
+where
+    C: Into<Node>,
+{
+    fn from_iter<T: IntoIterator<Item = C>>(children: T) -> Self {
+        let mut fragment = Fragment::new();
+        fragment.set_children(children.into_iter().map(Into::into).collect());
+        fragment
+    }
+}
@@ -0,0 +1,143 @@
+use crate::Widget;
+use crate::core::node::{Node, NodeType};
+use crate::modifiers::{Attributable, Classable, OnClickActionnable};
+use crate::prelude::{Icon, IconPack, Lucide, View};
+use crate::widgets::menu::Menu;
+
+/// A single entry of a [`Menu`].
+///
+/// Attach the behaviour of the item with [`OnClickActionnable::on_click`], the same way as for a `Button`.
+#[derive(Widget, Classable, Attributable, Clone, Debug)]
+#[widget(style = "./style.scss")]
+pub struct MenuItem {
+    node: Node,
+    pub label: String,
+    pub icon: Option<Box<dyn IconPack>>,
+    pub shortcut: Option<String>,
+    pub is_destructive: bool,
+    pub is_disabled: bool,
+    pub checked: Option<bool>,
+    pub submenu: Option<Box<Menu>>,
+}
+
+impl MenuItem {
+    /// Item displaying `label`, without icon nor shortcut
+    pub fn new(label: &str) -> Self {
+        Self {
+            node: Default::default(),
+            label: label.to_string(),
+            icon: None,
+            shortcut: None,
+            is_destructive: false,
+            is_disabled: false,
+            checked: None,
+            submenu: None,
+        }
+    }
+
+    /// Set item's icon
+    pub fn icon<T>(&mut self, icon: T) -> &mut Self
+    where
+        T: 'static + IconPack,
+    {
+        self.icon = Some(Box::new(icon));
+        self
+    }
+
+    /// Display a keyboard shortcut hint, for example `"Ctrl+D"`.
+    ///
+    /// This is only a hint, the shortcut itself is not bound by viewy.
+    pub fn shortcut(&mut self, shortcut: &str) -> &mut Self {
+        self.shortcut = Some(shortcut.to_string());
+        self
+    }
+
+    /// Turn the item to the destructive theme variant for destructive actions
+    pub fn destructive(&mut self) -> &mut Self {
+        self.is_destructive = true;
+        self
+    }
+
+    /// Keep the item visible but prevent it from being activated
+    pub fn disabled(&mut self) -> &mut Self {
+        self.is_disabled = true;
+        self
+    }
+
+    /// Make the item checkable, with its initial state
+    pub fn checkable(&mut self, checked: bool) -> &mut Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    /// Open a nested menu when this item is activated
+    pub fn submenu(&mut self, submenu: Menu) -> &mut Self {
+        self.submenu = Some(Box::new(submenu));
+        self
+    }
+
+    fn render(&mut self) {
+        self.add_class("menu__item").set_attr("tabindex", "-1");
+
+        match self.checked {
+            Some(checked) => {
+                self.set_attr("role", "menuitemcheckbox")
+                    .set_attr("aria-checked", &checked.to_string());
+            }
+            None => {
+                self.set_attr("role", "menuitem");
+            }
+        }
+
+        if self.is_destructive {
+            self.add_class("menu__item--destructive");
+        }
+        if self.is_disabled {
+            self.add_class("menu__item--disabled")
+                .set_attr("aria-disabled", "true");
+        }
+
+        let mut icon_container = View::new();
+        icon_container
+            .add_class("menu__item-icon")
+            .set_attr("aria-hidden", "true");
+        icon_container.node.node_type = NodeType::Normal("span");
+        if self.checked.is_some() {
+            let mut check_icon = Icon::new(Lucide::Check);
+            check_icon.size(16).add_class("menu__item-check");
+            icon_container.node.children.push(check_icon.into());
+        } else if let Some(item_icon) = self.icon.clone() {
+            let mut icon = Icon::new(item_icon);
+            icon.size(16);
+            icon_container.node.children.push(icon.into());
+        }
+        self.node.children.push(icon_container.into());
+
+        let mut item_label = View::new();
+        item_label.add_class("menu__item-label");
+        item_label.node.node_type = NodeType::Normal("span");
+        item_label.text = Some(self.label.clone());
+        self.node.children.push(item_label.into());
+
+        if let Some(shortcut) = self.shortcut.clone() {
+            self.set_attr("aria-keyshortcuts", &shortcut);
+            let mut shortcut_hint = View::new();
+            shortcut_hint
+                .add_class("menu__item-shortcut")
+                .set_attr("aria-hidden", "true");
+            shortcut_hint.node.node_type = NodeType::Normal("kbd");
+            shortcut_hint.text = Some(shortcut);
+            self.node.children.push(shortcut_hint.into());
+        }
+
+        if self.submenu.is_some() {
+            self.set_attr("aria-haspopup", "menu")
+                .set_attr("aria-expanded", "false");
+            let mut chevron_icon = Icon::new(Lucide::ChevronRight);
+            chevron_icon.size(16).add_class("menu__item-chevron");
+            self.node.children.push(chevron_icon.into());
+        }
+    }
+}
+
+impl OnClickActionnable for MenuItem {}
@@ -0,0 +1,191 @@
+use crate::Widget;
+use crate::core::node::{Node, NodeType};
+use crate::modifiers::{Attributable, Classable};
+use crate::prelude::View;
+use short_uuid::short;
+
+mod item;
+pub use item::*;
+
+/// A labelled group of items inside a [`Menu`]
+#[derive(Debug, Clone)]
+pub struct MenuSection {
+    pub label: Option<String>,
+    pub items: Vec<MenuItem>,
+}
+
+impl MenuSection {
+    /// A section with a visible `label`, also used as its accessible name
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: Some(label.to_string()),
+            items: vec![],
+        }
+    }
+
+    /// A section without a visible label, only separated from its siblings
+    pub fn unlabelled() -> Self {
+        Self {
+            label: None,
+            items: vec![],
+        }
+    }
+
+    /// Add an item at the end of the section
+    pub fn append_item(&mut self, item: MenuItem) -> &mut Self {
+        self.items.push(item);
+        self
+    }
+}
+
+/// An entry of a [`Menu`], in display order
+#[derive(Debug, Clone)]
+pub enum MenuEntry {
+    Item(Box<MenuItem>),
+    Separator,
+    Section(MenuSection),
+}
+
+/// A list of actions displayed in a floating panel.
+///
+/// The menu is rendered inline within its trigger, so opening it never needs a network round trip.
+/// ```rust,no_run
+/// use viewy::prelude::*;
+/// Button::new("More", ButtonStyle::Outlined).menu({
+///     let mut menu = Menu::new();
+///     menu.append_item(MenuItem::new("Rename").shortcut("F2").clone())
+///         .append_separator()
+///         .append_item(MenuItem::new("Delete").destructive().clone());
+///     menu
+/// });
+/// ```
+#[derive(Widget, Classable, Attributable, Clone, Debug)]
+#[widget(style = "./style.scss")]
+pub struct Menu {
+    node: Node,
+    label: Option<String>,
+    entries: Vec<MenuEntry>,
+}
+
+impl Default for Menu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Menu {
+    /// An empty menu, to fill with items, separators and sections
+    pub fn new() -> Self {
+        Self {
+            node: Default::default(),
+            label: None,
+            entries: vec![],
+        }
+    }
+
+    /// Accessible name of the menu
+    pub fn label(&mut self, label: &str) -> &mut Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Add an item at the end of the menu
+    pub fn append_item(&mut self, item: MenuItem) -> &mut Self {
+        self.entries.push(MenuEntry::Item(Box::new(item)));
+        self
+    }
+
+    /// Add a line separating the items before it from the ones after it
+    pub fn append_separator(&mut self) -> &mut Self {
+        self.entries.push(MenuEntry::Separator);
+        self
+    }
+
+    /// Add a group of items at the end of the menu
+    pub fn append_section(&mut self, section: MenuSection) -> &mut Self {
+        self.entries.push(MenuEntry::Section(section));
+        self
+    }
+
+    /// Render the menu inside a `<template>` so it can be attached to a trigger without being displayed.
+    pub(crate) fn into_template(self, template_id: &str) -> Node {
+        Node {
+            node_type: NodeType::Normal("template"),
+            html_id: Some(template_id.to_string()),
+            children: vec![self.into()],
+            ..Node::default()
+        }
+    }
+
+    fn build_item(mut item: MenuItem) -> Node {
+        let Some(submenu) = item.submenu.clone() else {
+            return item.into();
+        };
+
+        let submenu_id = format!("menu_{}", short!());
+        item.set_attr("aria-controls", &submenu_id);
+
+        let mut submenu = *submenu;
+        submenu
+            .add_class("menu--submenu")
+            .set_attr("id", &submenu_id)
+            .set_attr("hidden", "hidden");
+
+        let mut anchor = View::new();
+        anchor
+            .add_class("menu__submenu-anchor")
+            .set_attr("role", "none");
+        anchor.node.children.push(item.into());
+        anchor.node.children.push(submenu.into());
+        anchor.into()
+    }
+
+    fn build_section(section: MenuSection) -> Node {
+        let mut group = View::new();
+        group.add_class("menu__section").set_attr("role", "group");
+
+        if let Some(label) = &section.label {
+            let label_id = format!("menu_section_{}", short!());
+            group.set_attr("aria-labelledby", &label_id);
+
+            let mut section_label = View::new();
+            section_label
+                .add_class("menu__section-label")
+                .set_attr("id", &label_id)
+                .set_attr("role", "presentation");
+            section_label.text = Some(label.clone());
+            group.node.children.push(section_label.into());
+        }
+
+        for item in section.items {
+            group.node.children.push(Self::build_item(item));
+        }
+        group.into()
+    }
+
+    fn render(&mut self) {
+        self.add_class("menu")
+            .set_attr("role", "menu")
+            .set_attr("tabindex", "-1")
+            .set_attr("data-v-menu", "true");
+
+        if let Some(label) = self.label.clone() {
+            self.set_attr("aria-label", &label);
+        }
+
+        for entry in std::mem::take(&mut self.entries) {
+            let entry_node = match entry {
+                MenuEntry::Item(item) => Self::build_item(*item),
+                MenuEntry::Separator => {
+                    let mut separator = View::new();
+                    separator
+                        .add_class("menu__separator")
+                        .set_attr("role", "separator");
+                    separator.into()
+                }
+                MenuEntry::Section(section) => Self::build_section(section),
+            };
+            self.node.children.push(entry_node);
+        }
+    }
+}
@@ -1,14 +1,17 @@
-use crate::core::widget::Widget;
+use crate::core::widget::WidgetStyle;
 
 pub mod button;
 pub mod form;
+pub mod fragment;
 pub mod icon;
+pub mod menu;
 pub mod picker;
 #[cfg(feature = "rich-text-area")]
 pub mod rich_text_area;
 pub mod select;
 pub mod stack;
 pub mod tabs;
+pub mod theme_switcher;
 pub mod view;
 
 pub mod text;
@@ -31,16 +34,27 @@ pub mod tag;
 #[cfg(feature = "sortable-stack")]
 pub mod sortable_stack;
 
-pub const fn get_all_stylesheet() -> &'static [&'static str; 9] {
-    &[
-        view::View::STYLE,
-        button::Button::STYLE,
-        icon::Icon::STYLE,
-        select::Select::STYLE,
-        picker::Picker::STYLE,
-        stack::VStack::STYLE,
-        stack::HStack::STYLE,
-        text::Text::STYLE,
-        tabs::TabContainer::STYLE,
-    ]
+/// Style of a widget, from its type name
+pub(crate) fn get_widget_stylesheet(widget: &str) -> Option<&'static str> {
+    inventory::iter::<WidgetStyle>
+        .into_iter()
+        .find(|widget_style| widget_style.widget == widget)
+        .map(|widget_style| widget_style.style)
+}
+
+/// Styles of every widget deriving `Widget`, in this crate or any other linked in the binary
+pub fn get_all_stylesheet() -> Vec<&'static str> {
+    let mut widget_styles = inventory::iter::<WidgetStyle>
+        .into_iter()
+        .collect::<Vec<&WidgetStyle>>();
+    // Link order is not specified, keep the stylesheet stable between builds
+    widget_styles.sort_by_key(|widget_style| widget_style.widget);
+
+    let mut styles: Vec<&'static str> = vec![];
+    for widget_style in widget_styles {
+        if !styles.contains(&widget_style.style) {
+            styles.push(widget_style.style);
+        }
+    }
+    styles
 }
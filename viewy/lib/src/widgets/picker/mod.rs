@@ -68,7 +68,10 @@ pub enum PickerStyle {
 }
 
 #[derive(Widget, Classable, Attributable)]
-#[widget(style = "./style.css")]
+#[widget(
+    style = "./style.scss",
+    script = "../../../static/js/src/widgets/picker.js"
+)]
 pub struct Picker {
     node: Node,
     style: PickerStyle,
@@ -62,7 +62,10 @@ impl SelectGroup {
 }
 
 #[derive(Widget, Classable, Attributable)]
-#[widget(style = "./style.css")]
+#[widget(
+    style = "./style.css",
+    script = "../../../static/js/src/widgets/picker.js"
+)]
 pub struct Select {
     node: Node,
     label: Option<String>,
@@ -6,7 +6,10 @@ use crate::prelude::Classable;
 use crate::prelude::{Attributable, Cardifiable, View};
 
 #[derive(Widget, Classable, Attributable, Cardifiable)]
-#[widget(style = "./style.scss")]
+#[widget(
+    style = "./style.scss",
+    script = "../../../static/js/src/widgets/tabs.js"
+)]
 pub struct TabContainer {
     node: Node,
     keep_content_mounted: bool,
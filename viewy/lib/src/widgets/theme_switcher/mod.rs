@@ -0,0 +1,92 @@
+use crate::Widget;
+use crate::core::node::Node;
+use crate::core::theme::{THEME_COOKIE, Theme};
+use crate::modifiers::{Attributable, Classable};
+use crate::prelude::{Picker, PickerOption, PickerStyle};
+
+/// Segmented picker letting the user choose between the light, dark and automatic themes, and the
+/// high contrast one when enabled with [`ThemeSwitcher::high_contrast`].
+///
+/// The choice is stored in the [`THEME_COOKIE`] cookie and applied to the page without reloading
+/// it, the Rocket and axum bindings then render the next pages with it.
+#[derive(Widget, Classable, Attributable)]
+#[widget(
+    style = "./style.scss",
+    script = "../../../static/js/src/widgets/theme_switcher.js"
+)]
+pub struct ThemeSwitcher {
+    node: Node,
+    label: Option<String>,
+    theme: Theme,
+    light_label: String,
+    dark_label: String,
+    auto_label: String,
+    high_contrast_label: Option<String>,
+}
+
+impl ThemeSwitcher {
+    /// Switcher with the light, dark and automatic options, the automatic one being selected
+    pub fn new() -> Self {
+        Self {
+            node: Default::default(),
+            label: None,
+            theme: Theme::Auto,
+            light_label: "Light".to_string(),
+            dark_label: "Dark".to_string(),
+            auto_label: "Auto".to_string(),
+            high_contrast_label: None,
+        }
+    }
+
+    /// Label displayed above the options
+    pub fn label(&mut self, label: &str) -> &mut Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Theme selected when the page is rendered, the script selects the one stored in the cookie
+    pub fn selected(&mut self, theme: Theme) -> &mut Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Labels of the light, dark and automatic options
+    pub fn option_labels(&mut self, light: &str, dark: &str, auto: &str) -> &mut Self {
+        self.light_label = light.to_string();
+        self.dark_label = dark.to_string();
+        self.auto_label = auto.to_string();
+        self
+    }
+
+    /// Add an option for the high contrast theme, labelled `label`
+    pub fn high_contrast(&mut self, label: &str) -> &mut Self {
+        self.high_contrast_label = Some(label.to_string());
+        self
+    }
+
+    fn render(&mut self) {
+        self.add_class("theme-switcher");
+
+        let mut picker = Picker::new(THEME_COOKIE, self.theme.as_str(), PickerStyle::Segmented);
+        if let Some(label) = &self.label {
+            picker.label(label);
+        }
+        for (label, theme) in [
+            (&self.light_label, Theme::Light),
+            (&self.dark_label, Theme::Dark),
+            (&self.auto_label, Theme::Auto),
+        ] {
+            picker.append_option(PickerOption::new(label, theme.as_str()));
+        }
+        if let Some(label) = &self.high_contrast_label {
+            picker.append_option(PickerOption::new(label, Theme::HighContrast.as_str()));
+        }
+        self.node.children.push(picker.into());
+    }
+}
+
+impl Default for ThemeSwitcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -2,6 +2,7 @@ use crate::core::node::Node;
 use crate::core::widget::Widget;
 use crate::modifiers::{
     Appendable, Attributable, Cardifiable, Classable, Colorable, Dimensionable,
+    OnClickActionnable,
 };
 
 #[derive(Widget, Appendable, Colorable, Classable, Dimensionable, Cardifiable, Attributable)]
@@ -19,3 +20,5 @@ impl View {
 
     pub fn render(&mut self) {}
 }
+
+impl OnClickActionnable for View {}
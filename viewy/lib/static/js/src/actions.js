@@ -1,7 +1,12 @@
 import { getVOnEventNames, querySelectorByAttrPrefix } from "./helpers.js";
 import { actions } from "./widgets/popup.js";
+import { init as initConfirm } from "./widgets/confirm.js";
 
 export function init(root) {
+  for (const el of root.querySelectorAll("[data-v-confirm-event]")) {
+    initConfirm(el);
+  }
+
   for (const el of querySelectorByAttrPrefix("data-v-on-", root)) {
     for (const eventName of getVOnEventNames(el)) {
       let action = el.getAttribute(`data-v-on-${eventName}`);
@@ -18,6 +23,20 @@ export function init(root) {
             el.addEventListener(eventName, () => popover.actions[action](el));
           });
           break;
+        case "open_menu":
+          import("viewy/widgets/menu.js").then((menu) => {
+            el.addEventListener(eventName, (event) =>
+              menu.actions[action](el, event),
+            );
+          });
+          break;
+        case "request":
+          import("viewy/widgets/request.js").then((request) => {
+            el.addEventListener(eventName, (event) =>
+              request.actions[action](el, event),
+            );
+          });
+          break;
         case "open_sheet":
           import("viewy/widgets/sheet.js").then((sheet) => {
             el.addEventListener(eventName, () => sheet.actions[action](el));
@@ -24,12 +24,29 @@ export async function load_injectable_content(url, injection_root) {
       "x-viewy-render-mode": "ContentOnly",
     },
   });
-  let injectable_content = await res.text();
+  inject_content(await res.text(), injection_root);
+}
+
+// Swap the streamed components of injected content into their placeholder
+function resolve_deferred(container) {
+  container.querySelectorAll("template[data-v-resolved]").forEach((template) => {
+    let placeholder = container.querySelector(
+      `#v-deferred-${template.dataset.vResolved}`,
+    );
+    if (placeholder) {
+      placeholder.replaceWith(template.content);
+    }
+    template.remove();
+  });
+}
+
+export function inject_content(injectable_content, injection_root) {
   let old_class_list = injection_root.classList;
   let old_dataset = injection_root.dataset;
   //injection_root.insertAdjacentHTML("beforeend", injectable_content);
   let container = injection_root.cloneNode();
   container.innerHTML = injectable_content;
+  resolve_deferred(container);
   let result = morphdom(injection_root, container, {
     onElUpdated(el) {
       if (el.__hasListeners) {
@@ -48,6 +65,12 @@ export async function load_injectable_content(url, injection_root) {
   startViewy(result);
 }
 
+if (document.body && document.body.hasAttribute("data-v-boost")) {
+  import("./navigation.js").then((navigation) => {
+    navigation.init();
+  });
+}
+
 window.addEventListener("startViewy", (event) => {
   let root = event.detail.root;
   console.log("Viewy started", root);
@@ -58,18 +81,16 @@ window.addEventListener("startViewy", (event) => {
     });
   }
 
-  if (root.querySelector(".tab-container")) {
-    import("viewy/widgets/tabs.js").then((tabs) => {
-      tabs.init(root);
-    });
+  // Scripts declared by the widgets with `#[widget(script = "...")]`, served by viewy
+  let script_ids = new Set(
+    [...root.querySelectorAll("[data-v-script]")].map((el) => el.dataset.vScript),
+  );
+  if (typeof root.matches === "function" && root.matches("[data-v-script]")) {
+    script_ids.add(root.dataset.vScript);
   }
-
-  if (
-    root.querySelector(".select, .picker") ||
-    (typeof root.matches === "function" && root.matches(".select, .picker"))
-  ) {
-    import("viewy/widgets/picker.js").then((picker) => {
-      picker.init(root);
+  for (const script_id of script_ids) {
+    import(new URL(`../widgets/${script_id}.js`, import.meta.url)).then((widget) => {
+      widget.init?.(root);
     });
   }
   initActions(root);
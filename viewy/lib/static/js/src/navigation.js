@@ -0,0 +1,125 @@
+import { inject_content } from "viewy";
+
+const REGION_SELECTOR = "[data-v-page-content]";
+
+function current_layout() {
+  const meta = document.querySelector("meta[name='viewy-layout']");
+  return meta ? meta.getAttribute("content") : null;
+}
+
+function save_scroll() {
+  history.replaceState(
+    { ...history.state, viewy: true, scrollX: scrollX, scrollY: scrollY },
+    "",
+  );
+}
+
+function restore_scroll(url, state) {
+  if (state && state.scrollY !== undefined) {
+    scrollTo(state.scrollX, state.scrollY);
+    return;
+  }
+  const target = url.hash ? document.getElementById(url.hash.slice(1)) : null;
+  if (target) {
+    target.scrollIntoView();
+  } else {
+    scrollTo(0, 0);
+  }
+}
+
+function is_boostable(link, event) {
+  if (
+    event.defaultPrevented ||
+    event.button !== 0 ||
+    event.metaKey ||
+    event.ctrlKey ||
+    event.shiftKey ||
+    event.altKey
+  ) {
+    return false;
+  }
+  if (
+    (link.target && link.target !== "_self") ||
+    link.hasAttribute("download") ||
+    link.closest("[data-v-boost='false']") ||
+    Array.from(link.attributes).some((attr) =>
+      attr.name.startsWith("data-v-on-"),
+    )
+  ) {
+    return false;
+  }
+  const url = new URL(link.href, location.href);
+  if (url.origin !== location.origin) {
+    return false;
+  }
+  // Same page anchors are left to the browser
+  return !(
+    url.hash &&
+    url.pathname === location.pathname &&
+    url.search === location.search
+  );
+}
+
+async function navigate(url, { push, state }) {
+  const region = document.querySelector(REGION_SELECTOR);
+  if (!region) {
+    location.assign(url);
+    return;
+  }
+
+  let res;
+  try {
+    res = await fetch(url, {
+      headers: {
+        "x-viewy-render-mode": "ContentOnly",
+      },
+    });
+  } catch (error) {
+    location.assign(url);
+    return;
+  }
+
+  // Only the content is swapped, the page must share the current layout
+  const layout = res.headers.get("x-viewy-layout");
+  if (!res.ok || !layout || layout !== current_layout()) {
+    location.assign(res.ok && res.redirected ? res.url : url);
+    return;
+  }
+
+  const final_url = new URL(res.redirected ? res.url : url.href);
+  final_url.hash = url.hash;
+  if (push) {
+    history.pushState({ viewy: true }, "", final_url);
+  } else if (final_url.href !== location.href) {
+    history.replaceState({ ...history.state, viewy: true }, "", final_url);
+  }
+
+  const title = res.headers.get("x-viewy-title");
+  if (title !== null) {
+    document.title = decodeURIComponent(title);
+  }
+  inject_content(await res.text(), region);
+  restore_scroll(final_url, state);
+}
+
+export function init() {
+  history.scrollRestoration = "manual";
+  save_scroll();
+
+  document.addEventListener("click", (event) => {
+    const link = event.target.closest ? event.target.closest("a[href]") : null;
+    if (!link || !is_boostable(link, event)) {
+      return;
+    }
+    event.preventDefault();
+    save_scroll();
+    navigate(new URL(link.href, location.href), { push: true });
+  });
+
+  window.addEventListener("popstate", (event) => {
+    if (!event.state || !event.state.viewy) {
+      return;
+    }
+    navigate(new URL(location.href), { push: false, state: event.state });
+  });
+}
@@ -0,0 +1,136 @@
+function build_button(label, classes) {
+  let button = document.createElement("button");
+  button.type = "button";
+  button.classList.add("button", ...classes);
+  let text = document.createElement("span");
+  text.textContent = label;
+  button.append(text);
+  return button;
+}
+
+function close_confirm(popup) {
+  popup.addEventListener(
+    "transitionend",
+    () => {
+      popup.remove();
+    },
+    { once: true },
+  );
+  popup.classList.remove("visible");
+}
+
+function ask_confirmation(el) {
+  return new Promise((resolve) => {
+    const previously_focused = document.activeElement;
+    const confirm_id = `confirm_${Math.random().toString(36).slice(2)}`;
+
+    let popup = document.createElement("div");
+    popup.classList.add("popup", "popup--confirm");
+
+    let popup_window = document.createElement("div");
+    popup_window.classList.add("popup__window");
+    popup_window.setAttribute("role", "alertdialog");
+    popup_window.setAttribute("aria-modal", "true");
+    popup_window.setAttribute("aria-labelledby", `${confirm_id}-title`);
+    popup_window.setAttribute("aria-describedby", `${confirm_id}-message`);
+
+    let content = document.createElement("div");
+    content.classList.add("popup__window__window-content", "confirm");
+
+    let title = document.createElement("h2");
+    title.id = `${confirm_id}-title`;
+    title.classList.add("text", "text--h2", "confirm__title");
+    title.textContent = el.dataset.vConfirmTitle;
+
+    let message = document.createElement("p");
+    message.id = `${confirm_id}-message`;
+    message.classList.add("text", "text--body", "confirm__message");
+    message.textContent = el.dataset.vConfirmMessage;
+
+    let cancel_button = build_button(el.dataset.vCancelLabel, [
+      "button--outlined",
+    ]);
+    let confirm_button = build_button(
+      el.dataset.vConfirmLabel,
+      el.dataset.vConfirmDestructive === "true"
+        ? ["button--filled", "button--filled--destructive"]
+        : ["button--filled"],
+    );
+
+    let buttons = document.createElement("div");
+    buttons.classList.add("confirm__buttons");
+    buttons.append(cancel_button, confirm_button);
+
+    content.append(title, message, buttons);
+    popup_window.append(content);
+    popup.append(popup_window);
+    document.body.append(popup);
+
+    const answer = (confirmed) => {
+      close_confirm(popup);
+      if (previously_focused) {
+        previously_focused.focus();
+      }
+      resolve(confirmed);
+    };
+
+    cancel_button.addEventListener("click", () => answer(false));
+    confirm_button.addEventListener("click", () => answer(true));
+    popup.addEventListener("click", (e) => {
+      if (e.target === popup) {
+        answer(false);
+      }
+    });
+    popup.addEventListener("keydown", (e) => {
+      if (e.key === "Escape") {
+        e.preventDefault();
+        answer(false);
+      }
+      if (e.key === "Tab") {
+        e.preventDefault();
+        (document.activeElement === cancel_button
+          ? confirm_button
+          : cancel_button
+        ).focus();
+      }
+    });
+
+    popup.getBoundingClientRect();
+    requestAnimationFrame(() => {
+      popup.classList.add("visible");
+      cancel_button.focus();
+    });
+  });
+}
+
+export function init(el) {
+  const event_name = el.dataset.vConfirmEvent;
+  if (el.dataset.vConfirmInit === "true") {
+    return;
+  }
+  el.dataset.vConfirmInit = "true";
+
+  el.addEventListener(
+    event_name,
+    async (event) => {
+      if (el.__vConfirmed) {
+        el.__vConfirmed = false;
+        return;
+      }
+      event.preventDefault();
+      event.stopImmediatePropagation();
+
+      if (await ask_confirmation(el)) {
+        el.__vConfirmed = true;
+        if (event_name === "click") {
+          el.click();
+        } else {
+          el.dispatchEvent(
+            new MouseEvent(event_name, { bubbles: true, cancelable: true }),
+          );
+        }
+      }
+    },
+    { capture: true },
+  );
+}
@@ -0,0 +1,369 @@
+import { computePosition, flip, shift, offset, autoUpdate } from "floating-ui";
+
+import { startViewy } from "viewy";
+
+const ITEM_SELECTOR = '[role="menuitem"], [role="menuitemcheckbox"]';
+
+let openedMenu = null;
+
+function getItems(menu) {
+  return Array.from(menu.querySelectorAll(ITEM_SELECTOR)).filter(
+    (item) => item.closest(".menu") === menu,
+  );
+}
+
+function getEnabledItems(menu) {
+  return getItems(menu).filter(
+    (item) => item.getAttribute("aria-disabled") !== "true",
+  );
+}
+
+function focusItem(menu, item) {
+  getItems(menu).forEach((other) => other.setAttribute("tabindex", "-1"));
+  if (item) {
+    item.setAttribute("tabindex", "0");
+    item.focus();
+  }
+}
+
+function focusBoundary(menu, first) {
+  const items = getEnabledItems(menu);
+  focusItem(menu, first ? items[0] : items[items.length - 1]);
+}
+
+function moveFocus(menu, step) {
+  const items = getEnabledItems(menu);
+  if (items.length === 0) {
+    return;
+  }
+  const currentIndex = items.indexOf(document.activeElement);
+  const nextIndex =
+    currentIndex < 0
+      ? 0
+      : (currentIndex + step + items.length) % items.length;
+  focusItem(menu, items[nextIndex]);
+}
+
+function focusByCharacter(menu, character) {
+  const items = getEnabledItems(menu);
+  const start = items.indexOf(document.activeElement) + 1;
+  const ordered = items.slice(start).concat(items.slice(0, start));
+  const match = ordered.find((item) =>
+    item.textContent.trim().toLowerCase().startsWith(character.toLowerCase()),
+  );
+  if (match) {
+    focusItem(menu, match);
+  }
+}
+
+function position(reference, menu, placement) {
+  return autoUpdate(reference, menu, () => {
+    computePosition(reference, menu, {
+      placement,
+      strategy: menu.classList.contains("menu--submenu") ? "fixed" : "absolute",
+      middleware: [offset(4), flip(), shift({ padding: 5 })],
+    }).then(({ x, y }) => {
+      Object.assign(menu.style, {
+        left: `${x}px`,
+        top: `${y}px`,
+      });
+    });
+  });
+}
+
+function openSubmenu(item, focusFirst) {
+  const submenu = document.getElementById(item.getAttribute("aria-controls"));
+  if (!submenu || item.getAttribute("aria-expanded") === "true") {
+    if (submenu && focusFirst) {
+      focusBoundary(submenu, true);
+    }
+    return;
+  }
+
+  closeSubmenus(item.closest(".menu"));
+  submenu.removeAttribute("hidden");
+  item.setAttribute("aria-expanded", "true");
+  submenu.__vStopPositioning = position(item, submenu, "right-start");
+  requestAnimationFrame(() => submenu.classList.add("visible"));
+  if (focusFirst) {
+    focusBoundary(submenu, true);
+  }
+}
+
+function closeSubmenus(menu) {
+  getItems(menu)
+    .filter((item) => item.getAttribute("aria-expanded") === "true")
+    .forEach((item) => {
+      const submenu = document.getElementById(
+        item.getAttribute("aria-controls"),
+      );
+      item.setAttribute("aria-expanded", "false");
+      if (submenu) {
+        closeSubmenus(submenu);
+        if (submenu.__vStopPositioning) {
+          submenu.__vStopPositioning();
+        }
+        submenu.classList.remove("visible");
+        submenu.setAttribute("hidden", "hidden");
+      }
+    });
+}
+
+function closeMenu(restoreFocus) {
+  if (!openedMenu) {
+    return;
+  }
+  const { menu, trigger, stopPositioning } = openedMenu;
+  openedMenu = null;
+
+  closeSubmenus(menu);
+  stopPositioning();
+  trigger.setAttribute("aria-expanded", "false");
+  menu.addEventListener("transitionend", () => menu.remove(), { once: true });
+  menu.classList.remove("visible");
+
+  if (restoreFocus) {
+    trigger.focus();
+  }
+}
+
+function syncCheckedState(menu, template, item) {
+  const renderedMenu = template.content.querySelector(".menu");
+  const index = Array.from(menu.querySelectorAll(ITEM_SELECTOR)).indexOf(item);
+  const templateItem = renderedMenu.querySelectorAll(ITEM_SELECTOR)[index];
+  if (templateItem) {
+    templateItem.setAttribute("aria-checked", item.getAttribute("aria-checked"));
+  }
+}
+
+function activateItem(menu, template, item) {
+  if (item.getAttribute("aria-disabled") === "true") {
+    return;
+  }
+
+  if (item.getAttribute("aria-haspopup") === "menu") {
+    openSubmenu(item, true);
+    return;
+  }
+
+  if (item.getAttribute("role") === "menuitemcheckbox") {
+    const checked = item.getAttribute("aria-checked") !== "true";
+    item.setAttribute("aria-checked", checked.toString());
+    syncCheckedState(menu, template, item);
+    item.dispatchEvent(
+      new CustomEvent("viewy:menu-check", {
+        bubbles: true,
+        detail: { checked },
+      }),
+    );
+    return;
+  }
+
+  closeMenu(!item.hasAttribute("href"));
+}
+
+function handleKeyboard(event, template) {
+  const menu = event.target.closest(".menu");
+  if (!menu) {
+    return;
+  }
+  const item = event.target.closest(ITEM_SELECTOR);
+
+  switch (event.key) {
+    case "ArrowDown":
+      event.preventDefault();
+      moveFocus(menu, 1);
+      break;
+    case "ArrowUp":
+      event.preventDefault();
+      moveFocus(menu, -1);
+      break;
+    case "Home":
+      event.preventDefault();
+      focusBoundary(menu, true);
+      break;
+    case "End":
+      event.preventDefault();
+      focusBoundary(menu, false);
+      break;
+    case "ArrowRight":
+      if (item && item.getAttribute("aria-haspopup") === "menu") {
+        event.preventDefault();
+        openSubmenu(item, true);
+      }
+      break;
+    case "ArrowLeft":
+    case "Escape":
+      if (menu.classList.contains("menu--submenu")) {
+        event.preventDefault();
+        const parentItem = document.querySelector(
+          `[aria-controls="${menu.id}"]`,
+        );
+        closeSubmenus(parentItem.closest(".menu"));
+        focusItem(parentItem.closest(".menu"), parentItem);
+      } else if (event.key === "Escape") {
+        event.preventDefault();
+        closeMenu(true);
+      }
+      break;
+    case "Enter":
+    case " ":
+      if (item) {
+        event.preventDefault();
+        if (item.getAttribute("aria-haspopup") === "menu") {
+          activateItem(menu, template, item);
+        } else {
+          item.click();
+        }
+      }
+      break;
+    case "Tab":
+      closeMenu(false);
+      break;
+    default:
+      if (event.key.length === 1 && !event.ctrlKey && !event.metaKey) {
+        focusByCharacter(menu, event.key);
+      }
+      break;
+  }
+}
+
+function buildMenu(trigger) {
+  const template = document.getElementById(trigger.dataset.vMenuTemplate);
+  const menu = template.content.querySelector(".menu").cloneNode(true);
+  menu.id = trigger.dataset.vTargetMenu;
+
+  menu.addEventListener("keydown", (event) => handleKeyboard(event, template));
+  menu.addEventListener("click", (event) => {
+    const item = event.target.closest(ITEM_SELECTOR);
+    if (!item) {
+      return;
+    }
+    if (item.getAttribute("aria-disabled") === "true") {
+      event.preventDefault();
+      event.stopImmediatePropagation();
+      return;
+    }
+    activateItem(item.closest(".menu"), template, item);
+  });
+  menu.addEventListener("mouseover", (event) => {
+    const item = event.target.closest(ITEM_SELECTOR);
+    if (!item || item.getAttribute("aria-disabled") === "true") {
+      return;
+    }
+    const itemMenu = item.closest(".menu");
+    if (item.getAttribute("aria-haspopup") === "menu") {
+      openSubmenu(item, false);
+    } else {
+      closeSubmenus(itemMenu);
+    }
+    focusItem(itemMenu, item);
+  });
+
+  return menu;
+}
+
+function virtualPointer(event) {
+  return {
+    getBoundingClientRect() {
+      return {
+        x: event.clientX,
+        y: event.clientY,
+        left: event.clientX,
+        top: event.clientY,
+        right: event.clientX,
+        bottom: event.clientY,
+        width: 0,
+        height: 0,
+      };
+    },
+  };
+}
+
+document.addEventListener("click", (event) => {
+  if (
+    openedMenu &&
+    !openedMenu.menu.contains(event.target) &&
+    !openedMenu.trigger.contains(event.target) &&
+    !event.target.closest(".menu--submenu")
+  ) {
+    closeMenu(false);
+  }
+});
+
+function isContextMenuKey(event) {
+  return (
+    event.key === "ContextMenu" || (event.key === "F10" && event.shiftKey)
+  );
+}
+
+document.addEventListener("keydown", (event) => {
+  if (!event.target.closest) {
+    return;
+  }
+  const trigger = isContextMenuKey(event)
+    ? event.target.closest('[data-v-on-contextmenu="open_menu"]')
+    : event.target.closest('[data-v-on-click="open_menu"]');
+  if (
+    !trigger ||
+    (!isContextMenuKey(event) &&
+      event.key !== "ArrowDown" &&
+      event.key !== "ArrowUp")
+  ) {
+    return;
+  }
+  event.preventDefault();
+  actions.open_menu(trigger, event);
+});
+
+export const actions = {
+  open_menu(trigger, event) {
+    const fromKeyboard = event && event.type === "keydown";
+    const isContextMenu =
+      event &&
+      (event.type === "contextmenu" ||
+        (fromKeyboard && isContextMenuKey(event)));
+    if (isContextMenu) {
+      event.preventDefault();
+    }
+
+    const alreadyOpened = openedMenu && openedMenu.trigger === trigger;
+    // Some browsers still fire `contextmenu` after the context menu key opened the menu
+    if (
+      alreadyOpened &&
+      openedMenu.fromKeyboard &&
+      event.type === "contextmenu"
+    ) {
+      return;
+    }
+    closeMenu(false);
+    if (alreadyOpened && !isContextMenu) {
+      return;
+    }
+
+    const menu = buildMenu(trigger);
+    document.body.append(menu);
+
+    const reference =
+      isContextMenu && !fromKeyboard ? virtualPointer(event) : trigger;
+    openedMenu = {
+      menu,
+      trigger,
+      fromKeyboard,
+      stopPositioning: position(
+        reference,
+        menu,
+        isContextMenu ? "right-start" : "bottom-start",
+      ),
+    };
+    trigger.setAttribute("aria-expanded", "true");
+
+    startViewy(menu);
+    menu.getBoundingClientRect();
+    requestAnimationFrame(() => {
+      menu.classList.add("visible");
+    });
+
+    focusBoundary(menu, !(event && event.key === "ArrowUp"));
+  },
+};
@@ -0,0 +1,88 @@
+import { load_injectable_content } from "viewy";
+import { show_toast } from "./toast.js";
+
+function csrf_token() {
+  const meta = document.querySelector("meta[name='csrf-token']");
+  return meta ? meta.getAttribute("content") : null;
+}
+
+function build_body(el, headers) {
+  if (el.dataset.vBodyForm) {
+    const form = document.getElementById(el.dataset.vBodyForm);
+    return form ? new FormData(form) : undefined;
+  }
+  if (el.dataset.vBodyJson) {
+    headers["content-type"] = "application/json";
+    return el.dataset.vBodyJson;
+  }
+  return undefined;
+}
+
+async function after_request(el, res) {
+  const toast_override = res.headers.get("x-viewy-toast");
+  switch (el.dataset.vThen) {
+    case "navigate":
+      window.location.assign(el.dataset.vThenUrl);
+      break;
+    case "follow_redirect":
+      window.location.assign(res.url);
+      break;
+    case "reload":
+      window.location.reload();
+      break;
+    case "reload_region": {
+      const region = document.getElementById(el.dataset.vThenRegion);
+      if (region) {
+        await load_injectable_content(el.dataset.vThenUrl, region);
+      }
+      break;
+    }
+    case "show_toast":
+      show_toast(toast_override || el.dataset.vThenMessage, "success");
+      return;
+  }
+  if (toast_override) {
+    show_toast(toast_override, "success");
+  }
+}
+
+export const actions = {
+  async request(el, event) {
+    if (event) {
+      event.preventDefault();
+    }
+    if (el.getAttribute("aria-busy") === "true") {
+      return;
+    }
+
+    const headers = {};
+    const token = csrf_token();
+    if (token) {
+      headers["x-csrf-token"] = token;
+    }
+    const body = build_body(el, headers);
+
+    el.setAttribute("aria-busy", "true");
+    try {
+      const res = await fetch(el.dataset.vUrl, {
+        method: el.dataset.vMethod,
+        headers,
+        body,
+        credentials: "same-origin",
+      });
+      if (!res.ok) {
+        show_toast(
+          res.headers.get("x-viewy-toast") ||
+            `${res.status} ${res.statusText}`.trim(),
+          "error",
+        );
+        return;
+      }
+      await after_request(el, res);
+    } catch (error) {
+      show_toast(error.message, "error");
+    } finally {
+      el.removeAttribute("aria-busy");
+    }
+  },
+};
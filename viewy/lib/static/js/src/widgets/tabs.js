@@ -1,4 +1,4 @@
-import {load_injectable_content} from "../core.js";
+import {load_injectable_content} from "viewy";
 
 export async function init(root) {
     let tabContainers = root.querySelectorAll('.tab-container');
@@ -0,0 +1,84 @@
+const THEME_COOKIE = "viewy_theme";
+const THEMES = ["light", "dark", "auto", "high-contrast"];
+const COLOR_SCHEMES = {
+  light: "light",
+  dark: "dark",
+};
+
+function findAll(root, selector) {
+  const matches = [];
+
+  if (typeof root.matches === "function" && root.matches(selector)) {
+    matches.push(root);
+  }
+
+  return matches.concat(Array.from(root.querySelectorAll(selector)));
+}
+
+function readTheme() {
+  const cookie = document.cookie
+    .split(";")
+    .map((cookie) => cookie.trim().split("="))
+    .find(([name]) => name === THEME_COOKIE);
+
+  if (!cookie) {
+    return null;
+  }
+
+  const theme = decodeURIComponent(cookie[1] || "");
+  return THEMES.includes(theme) ? theme : null;
+}
+
+function storeTheme(theme) {
+  document.cookie = `${THEME_COOKIE}=${encodeURIComponent(theme)}; Path=/; Max-Age=31536000; SameSite=Lax`;
+}
+
+function applyTheme(theme) {
+  [document.documentElement, document.body].forEach((element) => {
+    THEMES.forEach((name) => element.classList.remove(`app-theme--${name}`));
+    element.classList.add(`app-theme--${theme}`);
+  });
+
+  const colorScheme = document.querySelector('meta[name="color-scheme"]');
+  if (colorScheme) {
+    colorScheme.setAttribute("content", COLOR_SCHEMES[theme] || "light dark");
+  }
+
+  document.querySelectorAll('meta[name="theme-color"]').forEach((meta) => {
+    const color = meta.getAttribute(`data-v-${theme}`);
+    if (color) {
+      meta.setAttribute("content", color);
+    }
+  });
+}
+
+export function init(root) {
+  const storedTheme = readTheme();
+
+  findAll(root, ".theme-switcher").forEach((switcher) => {
+    if (switcher.dataset.vThemeSwitcherInit === "true") {
+      return;
+    }
+
+    switcher.dataset.vThemeSwitcherInit = "true";
+
+    if (storedTheme) {
+      const input = switcher.querySelector(
+        `.picker__item-input[value="${storedTheme}"]`,
+      );
+      if (input) {
+        input.checked = true;
+      }
+    }
+
+    switcher.addEventListener("change", (event) => {
+      const input = event.target;
+      if (!input.matches(".picker__item-input") || !input.checked) {
+        return;
+      }
+
+      storeTheme(input.value);
+      applyTheme(input.value);
+    });
+  });
+}
@@ -0,0 +1,34 @@
+function get_toast_container() {
+  let container = document.querySelector(".toast-container");
+  if (!container) {
+    container = document.createElement("div");
+    container.classList.add("toast-container");
+    container.setAttribute("role", "status");
+    container.setAttribute("aria-live", "polite");
+    document.body.append(container);
+  }
+  return container;
+}
+
+export function show_toast(message, kind = "info") {
+  let toast = document.createElement("div");
+  toast.classList.add("toast", `toast--${kind}`);
+  toast.textContent = message;
+  get_toast_container().append(toast);
+
+  toast.getBoundingClientRect();
+  requestAnimationFrame(() => {
+    toast.classList.add("visible");
+  });
+
+  setTimeout(() => {
+    toast.addEventListener(
+      "transitionend",
+      () => {
+        toast.remove();
+      },
+      { once: true },
+    );
+    toast.classList.remove("visible");
+  }, 4000);
+}
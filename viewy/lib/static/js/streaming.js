@@ -0,0 +1,11 @@
+// Swaps the components streamed after the page shell into their placeholder.
+// Loaded as a classic script so that it runs while the page is still streaming.
+window.viewyResolve = (id) => {
+  const template = document.querySelector(`template[data-v-resolved="${id}"]`);
+  const placeholder = document.getElementById(`v-deferred-${id}`);
+  if (template && placeholder) {
+    placeholder.replaceWith(template.content);
+  }
+  template?.nextElementSibling?.remove();
+  template?.remove();
+};